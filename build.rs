@@ -1,6 +1,11 @@
 use std::io;
 
 fn main() -> io::Result<()> {
+    // Icons and the elevation manifest only apply to Windows executables
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("windows") {
+        return Ok(());
+    }
+
     let mut binding = winres::WindowsResource::new();
     let win_res = binding
         .set_icon("src/assets/veritas.ico");
@@ -1,27 +1,34 @@
-use crate::core::config::Config;
-use crate::core::message_logger::MessageLogger;
+use crate::core::client;
+use crate::core::config::{Config, ConfigStore};
+use crate::core::file_logger;
+use crate::core::layout::{DetachedWindow, DockLayout, Pane};
+use crate::core::preferences::Session;
+use crate::core::paths::app_paths;
+use crate::core::live_stats::{LiveStatsServer, DEFAULT_LIVE_STATS_PORT};
 use crate::core::models::*;
-use crate::core::packet_handler::PacketHandler;
 use crate::core::updater::Updater;
 use eframe::egui::{self};
 use egui_toast::{Toast, ToastKind, ToastOptions, Toasts};
-use futures_util::FutureExt;
-use rust_socketio::Event;
-use rust_socketio::{
-    asynchronous::{Client, ClientBuilder},
-    Payload,
-};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::runtime::Runtime;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, watch, Mutex};
 use tokio::time::sleep;
+use crate::panels::compact_panel::COMPACT_SHORTCUT;
+use crate::panels::settings_window::SettingsTab;
+use crate::widgets::character_stats_widget::StatsSort;
+use crate::widgets::log_widget::LogView;
+use veritas_core::i18n::{self, tr};
+use veritas_core::message_logger::{LogCategory, LogLevel, MessageLogger};
+use veritas_core::packet_handler::PacketHandler;
+use veritas_core::snapshot::BattleSnapshot;
+use veritas_core::tr;
 
-#[derive(PartialEq, Clone)]
-pub enum Unit {
-    Turn,
-    ActionValue,
-}
+pub use crate::core::preferences::{GraphMode, Unit};
+
+pub const DEFAULT_WINDOW_SIZE: [f32; 2] = [1200.0, 800.0];
+pub const MIN_WINDOW_SIZE: [f32; 2] = [800.0, 600.0];
 
 pub struct UpdateState {
     pub downloaded: bool,
@@ -29,19 +36,36 @@ pub struct UpdateState {
 
 #[derive(Clone)]
 pub struct AppState {
-    pub is_sidebar_expanded: bool,
     pub is_window_pinned: bool,
+    pub is_compact: bool,
+    /// Window size to go back to when leaving compact mode
+    pub expanded_size: Option<egui::Vec2>,
+    /// Last size requested for the compact strip
+    pub compact_size: Option<egui::Vec2>,
     pub show_connection_settings: bool,
     pub show_preferences: bool,
     pub show_launcher: bool,
-    pub game_path: Option<String>,
-    pub graph_x_unit: Unit,
-    pub config: Config,
+    pub graph_mode: GraphMode,
+    pub config: ConfigStore,
     pub show_about: bool,
     pub show_updates: bool,
     pub checked_app_version: Option<String>,
     pub checked_dll_version: Option<String>,
     pub update_state: Arc<Mutex<UpdateState>>,
+    pub log_view: LogView,
+    pub stats_sort: StatsSort,
+    /// Turn whose hits are shown, 1-based like the turn plot
+    pub selected_turn: Option<usize>,
+    /// Character the hit histogram is limited to, all of them when `None`
+    pub histogram_character: Option<String>,
+    pub heatmap_unit: Unit,
+    pub settings_tab: SettingsTab,
+    pub layout: DockLayout,
+    /// Where each pane's window is now or was last, kept after it docks again
+    /// so popping it out reopens it in the same place
+    pub window_geometry: BTreeMap<Pane, DetachedWindow>,
+    /// Name being typed in the Save Layout window, `Some` while it is open
+    pub layout_name: Option<String>,
 }
 
 pub struct DamageAnalyzer {
@@ -55,6 +79,8 @@ pub struct DamageAnalyzer {
     pub runtime: Runtime,
     pub updater: Updater,
     pub toasts: Arc<Mutex<Toasts>>,
+    pub live_stats: LiveStatsServer,
+    config_rx: watch::Receiver<Config>,
 }
 
 impl DamageAnalyzer {
@@ -64,7 +90,28 @@ impl DamageAnalyzer {
 
         let message_logger = Arc::new(Mutex::new(MessageLogger::default()));
         let data_buffer = Arc::new(DataBuffer::new());
-        let packet_handler = PacketHandler::new(message_logger.clone(), data_buffer.clone());
+        let live_stats = LiveStatsServer::new();
+        let packet_handler = PacketHandler::new();
+        let config = ConfigStore::load();
+        let (preferences, mut session) = config.read(|config| {
+            let session = if config.preferences.restore_session {
+                config.session.clone()
+            } else {
+                let mut session = Session::default();
+                session.layout.activate(match config.preferences.default_graph_unit {
+                    Unit::Turn => Pane::TurnDamage,
+                    Unit::ActionValue => Pane::AvDamage,
+                });
+                session
+            };
+            (config.preferences.clone(), session)
+        });
+        session.layout.normalize();
+        i18n::set_language(preferences.language);
+        cc.egui_ctx.set_theme(preferences.theme.to_egui());
+        file_logger::set_level(preferences.log_level.to_log_level().to_level_filter());
+        live_stats.set_number_format(preferences.number_format);
+        config.read(|config| live_stats.set_colors(preferences.palette, &config.character_colors));
 
         let mut app = Self {
             server_addr: Mutex::new("127.0.0.1".to_string()).into(),
@@ -74,42 +121,71 @@ impl DamageAnalyzer {
             message_logger,
             is_there_update: Mutex::new(false).into(),
             state: AppState {
-                is_sidebar_expanded: false,
-                is_window_pinned: false,
+                is_window_pinned: session.window_pinned,
+                is_compact: false,
+                expanded_size: None,
+                compact_size: None,
                 show_connection_settings: false,
                 show_preferences: false,
                 show_launcher: false,
-                game_path: Config::load().game_path,
-                graph_x_unit: Unit::Turn,
-                config: Config::load(),
+                graph_mode: session.graph_mode,
+                config: config.clone(),
                 show_about: false,
                 show_updates: false,
                 checked_app_version: None,
                 checked_dll_version: None,
                 update_state: Arc::new(Mutex::new(UpdateState { downloaded: false })),
+                log_view: LogView {
+                    min_level: preferences.log_level,
+                    ..Default::default()
+                },
+                stats_sort: StatsSort::default(),
+                selected_turn: None,
+                histogram_character: None,
+                heatmap_unit: preferences.default_graph_unit,
+                settings_tab: SettingsTab::default(),
+                layout: session.layout.clone(),
+                window_geometry: session.window_geometry.clone(),
+                layout_name: None,
             },
             runtime: Runtime::new().unwrap(),
-            updater: Updater::new(),
+            updater: Updater::new(config.clone()),
             toasts: Arc::new(Mutex::new(
                 Toasts::new()
                     .anchor(egui::Align2::RIGHT_BOTTOM, (-10.0, -10.0))
                     .direction(egui::Direction::BottomUp),
             )),
+            live_stats,
+            config_rx: config.subscribe(),
         };
 
         // Enter the runtime so that `tokio::spawn` is available immediately.
         let _enter = app.runtime.enter();
 
         app.start_background_workers(&cc.egui_ctx, packet_handler);
+        app.apply_window_level(&cc.egui_ctx);
+
+        if app.state.config.read(|config| config.live_stats_enabled) {
+            app.start_live_stats();
+        }
+
+        if preferences.check_updates_on_startup {
+            app.check_updates_on_startup();
+        }
 
-        let toasts = app.toasts.clone();
-        let mut updater = app.updater.clone();
+        app
+    }
+
+    fn check_updates_on_startup(&mut self) {
+        let toasts = self.toasts.clone();
+        let message_logger = self.message_logger.clone();
+        let mut updater = self.updater.clone();
         let (version_tx, version_rx) = tokio::sync::oneshot::channel();
 
-        app.runtime.spawn(async move {
+        self.runtime.spawn(async move {
             if let Ok(mut toast_lock) = toasts.try_lock() {
                 toast_lock.add(Toast {
-                    text: "Checking for updates...".into(),
+                    text: tr("Checking for updates...").into(),
                     kind: ToastKind::Info,
                     options: ToastOptions::default().duration_in_seconds(0.5),
                     ..Default::default()
@@ -119,11 +195,15 @@ impl DamageAnalyzer {
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
             if let Some(new_version) = updater.check_app_update().await {
+                message_logger.lock().await.log(
+                    LogLevel::Info,
+                    LogCategory::Update,
+                    format!("Latest Veritas App release is {}", new_version),
+                );
                 if let Ok(mut toast_lock) = toasts.try_lock() {
                     if new_version != env!("CARGO_PKG_VERSION") {
                         toast_lock.add(Toast {
-                            text: format!("Veritas App version {} is available!", new_version)
-                                .into(),
+                            text: tr!("Veritas App version {} is available!", new_version).into(),
                             kind: ToastKind::Success,
                             options: ToastOptions::default().duration_in_seconds(5.0),
                             ..Default::default()
@@ -131,7 +211,7 @@ impl DamageAnalyzer {
                         let _ = version_tx.send(Some(new_version));
                     } else {
                         toast_lock.add(Toast {
-                            text: "Veritas App is up to date".into(),
+                            text: tr("Veritas App is up to date").into(),
                             kind: ToastKind::Info,
                             options: ToastOptions::default().duration_in_seconds(3.0),
                             ..Default::default()
@@ -141,13 +221,19 @@ impl DamageAnalyzer {
                 }
             }
 
-            if let Ok(()) = updater.update_dll().await {
+            // update_dll records the version it installs, so read the old one first
+            let previous_version = updater.current_dll_version();
+            if updater.update_dll().await.is_ok() {
                 if let Some(new_version) = updater.latest_dll_version() {
-                    let current_version = updater.current_dll_version();
-                    if current_version.is_none() || current_version.unwrap() != new_version {
+                    if previous_version.as_ref() != Some(&new_version) {
+                        message_logger.lock().await.log(
+                            LogLevel::Info,
+                            LogCategory::Update,
+                            format!("Updated Veritas to version {}", new_version),
+                        );
                         if let Ok(mut toast_lock) = toasts.try_lock() {
                             toast_lock.add(Toast {
-                                text: format!("Updated Veritas to version {}!", new_version).into(),
+                                text: tr!("Updated Veritas to version {}!", new_version).into(),
                                 kind: ToastKind::Success,
                                 options: ToastOptions::default().duration_in_seconds(5.0),
                                 ..Default::default()
@@ -159,10 +245,65 @@ impl DamageAnalyzer {
         });
 
         if let Ok(Some(new_version)) = version_rx.blocking_recv() {
-            app.state.checked_app_version = Some(new_version);
+            self.state.checked_app_version = Some(new_version);
         }
+    }
 
-        app
+    /// The layout with detached windows where they are now rather than where they opened
+    pub fn current_layout(&self) -> DockLayout {
+        let mut layout = self.state.layout.clone();
+        for window in &mut layout.detached {
+            if let Some(current) = self.state.window_geometry.get(&window.pane) {
+                *window = current.clone();
+            }
+        }
+        layout
+    }
+
+    /// Switches to `layout`, opening its detached windows where it saved them
+    pub fn load_layout(&mut self, mut layout: DockLayout) {
+        layout.normalize();
+        for window in &layout.detached {
+            self.state.window_geometry.insert(window.pane, window.clone());
+        }
+        self.state.layout = layout;
+        self.remember_session();
+    }
+
+    /// Saves layout, pin and graph options so the next launch can restore them
+    pub fn remember_session(&self) {
+        let session = Session {
+            layout: self.current_layout(),
+            window_pinned: self.state.is_window_pinned,
+            graph_mode: self.state.graph_mode,
+            window_geometry: self.state.window_geometry.clone(),
+        };
+        self.state.config.update(|config| config.session = session);
+    }
+
+    /// Reacts to settings changed from anywhere in the app
+    fn apply_config_changes(&mut self, ctx: &egui::Context) {
+        if !self.config_rx.has_changed().unwrap_or(false) {
+            return;
+        }
+        let (live_stats_enabled, preferences) = {
+            let config = self.config_rx.borrow_and_update();
+            self.live_stats.set_colors(config.preferences.palette, &config.character_colors);
+            (config.live_stats_enabled, config.preferences.clone())
+        };
+
+        i18n::set_language(preferences.language);
+        ctx.set_theme(preferences.theme.to_egui());
+        file_logger::set_level(preferences.log_level.to_log_level().to_level_filter());
+        self.live_stats.set_number_format(preferences.number_format);
+
+        if live_stats_enabled != self.live_stats.running_port().is_some() {
+            if live_stats_enabled {
+                self.start_live_stats();
+            } else {
+                self.stop_live_stats();
+            }
+        }
     }
 
     fn start_background_workers(&self, ctx: &egui::Context, packet_handler: PacketHandler) {
@@ -171,6 +312,45 @@ impl DamageAnalyzer {
         self.start_client_worker(payload_tx);
     }
 
+    pub fn start_live_stats(&mut self) {
+        let port = self
+            .state
+            .config
+            .read(|config| config.live_stats_port)
+            .unwrap_or(DEFAULT_LIVE_STATS_PORT);
+        match self.live_stats.start(&self.runtime, port) {
+            Ok(()) => {
+                self.message_logger.blocking_lock().log(
+                    LogLevel::Info,
+                    LogCategory::App,
+                    format!("Live stats server listening on http://127.0.0.1:{}/api/battle", port),
+                );
+            }
+            Err(e) => {
+                self.message_logger.blocking_lock().log(
+                    LogLevel::Error,
+                    LogCategory::App,
+                    format!("Failed to start live stats server: {}", e),
+                );
+                if let Ok(mut toast_lock) = self.toasts.try_lock() {
+                    toast_lock.add(Toast {
+                        text: tr!("Failed to start live stats server on port {}: {}", port, e).into(),
+                        kind: ToastKind::Error,
+                        options: ToastOptions::default().duration_in_seconds(5.0),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn stop_live_stats(&mut self) {
+        self.live_stats.stop();
+        self.message_logger
+            .blocking_lock()
+            .log(LogLevel::Info, LogCategory::App, "Live stats server stopped");
+    }
+
     fn start_packet_worker(
         &self,
         mut payload_rx: mpsc::Receiver<Packet>,
@@ -178,14 +358,59 @@ impl DamageAnalyzer {
         mut packet_handler: PacketHandler,
     ) {
         let is_there_update = self.is_there_update.clone();
+        let data_buffer = self.data_buffer.clone();
+        let message_logger = self.message_logger.clone();
+        let snapshot_tx = self.live_stats.publisher();
+        let event_tx = self.live_stats.event_sender();
+        let config = self.state.config.clone();
+        let mut config_rx = config.subscribe();
+        config_rx.mark_changed();
         self.runtime.spawn(async move {
             loop {
+                let mut lineup = None;
                 let mut is_there_update_lock = is_there_update.lock().await;
-                *is_there_update_lock = packet_handler.handle_packets(&mut payload_rx).await;
+                *is_there_update_lock = match payload_rx.try_recv() {
+                    Ok(packet) => {
+                        if config_rx.has_changed().unwrap_or(false) {
+                            let configured = config_rx.borrow_and_update().damage_log_dir.clone();
+                            packet_handler.set_output_dir(app_paths().damage_log_dir(configured.as_deref()));
+                        }
+                        let mut data_buffer = data_buffer.lock().await.unwrap();
+                        let output = packet_handler.handle_packet(&mut data_buffer, packet);
+
+                        let mut message_logger = message_logger.lock().await;
+                        for message in output.messages {
+                            message_logger.push(message);
+                        }
+                        for event in output.events {
+                            if let BattleEventKind::Lineup { avatars } = &event.kind {
+                                lineup = Some(avatars.clone());
+                            }
+                            // No subscribers is the usual case, so a failed send is expected
+                            let _ = event_tx.send(event);
+                        }
+                        if output.updated {
+                            snapshot_tx.send_replace(BattleSnapshot::from_battle(&data_buffer));
+                        }
+                        output.updated
+                    }
+                    Err(_) => false,
+                };
                 if *is_there_update_lock && !ctx.has_requested_repaint() {
                     ctx.request_repaint();
                 }
                 drop(is_there_update_lock);
+
+                // Saving colors for new characters writes the config file, so keep it
+                // off the runtime and clear of the locks the UI waits on every frame
+                if let Some(avatars) = lineup {
+                    let config = config.clone();
+                    tokio::task::spawn_blocking(move || {
+                        config.update(|config| {
+                            config.character_colors.assign(&avatars);
+                        });
+                    });
+                }
                 sleep(Duration::from_millis(1)).await;
             }
         });
@@ -195,81 +420,51 @@ impl DamageAnalyzer {
         &self,
         payload_tx: mpsc::Sender<Packet>,
     ) {
-        let server_addr = self.server_addr.clone();
-        let server_port = self.server_port.clone();
-        let connected = self.connected.clone();
-
-        // This is so verbose, but necessary
-        self.runtime.spawn(async move {
-            loop {
-                if !*connected.lock().await {
-                    let on_connected_status = connected.clone();
-                    let on_disconnected_status = connected.clone();
-                    let payload_tx = payload_tx.clone();
-
-                    let connected_callback = move |_payload: Payload, _socket: Client| {
-                        let on_connected_status = on_connected_status.clone();
-                        async move {
-                            *on_connected_status.lock().await = true;
-                        }
-                        .boxed()
-                    };
-
-                    let disconnected_callback = move |_payload: Payload, _socket: Client| {
-                        let on_disconnected_status = on_disconnected_status.clone();
-                        async move {
-                            *on_disconnected_status.lock().await = false;
-                        }
-                        .boxed()
-                    };
-
-                    let message_handler_callback  = move |event: Event, payload: Payload, _socket: Client| {
-                        let payload_tx = payload_tx.clone();
-                        async move {
-                            if let Event::Custom(e) = event {
-                                if let Payload::Text(text) = payload {
-                                    for msg in text {
-                                        let _ = payload_tx.send(Packet { r#type: e.clone(), data: msg }).await;
-                                    }
-                                }
-                            }
-                        }
-                        .boxed()
-                    };
-
-                    ClientBuilder::new(format!(
-                        "http://{}:{}/",
-                        &server_addr.lock().await,
-                        &server_port.lock().await
-                    ))
-                    .namespace("/")
-                    .on(Event::Connect, connected_callback)
-                    .on(Event::Error, disconnected_callback)
-                    .on_any(message_handler_callback)
-                    .reconnect(false)
-                    .connect()
-                    .await
-                    .ok();
-                }
-
-                sleep(Duration::from_secs(1)).await;
-            }
-        });
+        self.runtime.spawn(client::run_client(
+            self.server_addr.clone(),
+            self.server_port.clone(),
+            self.connected.clone(),
+            self.message_logger.clone(),
+            payload_tx,
+        ));
     }
 }
 
 impl eframe::App for DamageAnalyzer {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        self.show_menubar_panel(ctx, _frame);
-        self.show_statusbar_panel(ctx, _frame);
+        self.apply_config_changes(ctx);
+        if let Some(wait) = self.state.config.save_settled() {
+            ctx.request_repaint_after(wait);
+        }
+
+        if ctx.input_mut(|i| i.consume_shortcut(&COMPACT_SHORTCUT)) {
+            self.toggle_compact(ctx);
+        }
 
-        self.show_sidebar_panel(ctx, _frame);
-        self.show_av_panel(ctx, _frame);
+        if self.state.is_compact {
+            self.show_compact_panel(ctx);
+        } else {
+            self.show_menubar_panel(ctx, _frame);
+            self.show_statusbar_panel(ctx, _frame);
 
-        self.show_central_panel(ctx, _frame);
+            self.show_dock_panel(ctx);
+            self.show_turn_detail_window(ctx);
+        }
+        self.show_detached_windows(ctx);
 
         if let Ok(mut toasts) = self.toasts.try_lock() {
             toasts.show(ctx);
         }
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Detached windows may have moved since the session was last saved
+        self.remember_session();
+        self.state.config.save_pending();
+    }
+
+    // Transparent so the compact strip can be translucent, the panels paint over it otherwise
+    fn clear_color(&self, _visuals: &egui::Visuals) -> [f32; 4] {
+        [0.0; 4]
+    }
 }
@@ -0,0 +1,338 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Context;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use tokio::{
+    runtime::Runtime,
+    sync::{mpsc, Mutex},
+};
+use veritas_core::{
+    capture::{self, CaptureWriter},
+    helpers::NumberFormat,
+    i18n::{self, tr},
+    message_logger::MessageLogger,
+    models::{BattleData, BattleEventKind, Packet},
+    packet_handler::PacketHandler,
+    summary::{BattleSummary, DEFAULT_TOP_HITS},
+    tr,
+};
+
+use crate::core::{client, config::Config, paths::app_paths};
+
+#[derive(Parser)]
+#[command(name = "veritas", version, about = "Damage analyzer for Honkai: Star Rail")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Record battles without opening the window, writing a capture, CSVs and JSON summaries
+    Record {
+        /// Address of the injected veritas server
+        #[arg(long, default_value = "127.0.0.1")]
+        server: String,
+        #[arg(long, default_value = "1305")]
+        port: String,
+        /// Capture file to write, defaults to HSR_<timestamp>.jsonl in the damage log directory
+        #[arg(long)]
+        capture: Option<PathBuf>,
+        /// compact, full, scientific or cn; defaults to the number format preference
+        #[arg(long)]
+        numbers: Option<NumberFormat>,
+    },
+    /// Run a capture back through the analyzer, writing a CSV and JSON summary per battle
+    Replay {
+        capture: PathBuf,
+        /// Only print summaries, don't write any files
+        #[arg(long)]
+        dry_run: bool,
+        /// compact, full, scientific or cn; defaults to the number format preference
+        #[arg(long)]
+        numbers: Option<NumberFormat>,
+    },
+    /// Print summaries of damage log CSVs or captures; directories are expanded to their CSVs
+    Summarize {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Number of biggest hits to list per battle
+        #[arg(long, default_value_t = DEFAULT_TOP_HITS)]
+        top: usize,
+        /// compact, full, scientific or cn; defaults to the number format preference.
+        /// JSON output always has raw numbers.
+        #[arg(long)]
+        numbers: Option<NumberFormat>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Markdown,
+}
+
+#[derive(Serialize)]
+struct FileSummary {
+    file: String,
+    /// 1-based battle index within the file
+    battle: usize,
+    #[serde(flatten)]
+    summary: BattleSummary,
+}
+
+pub fn run(command: Command) -> anyhow::Result<()> {
+    let config = Config::read_only();
+    // Summaries are written in the GUI's language
+    i18n::set_language(config.preferences.language);
+    // `--numbers` if given, otherwise the GUI's number format preference
+    let number_format = |flag: Option<NumberFormat>| flag.unwrap_or(config.preferences.number_format);
+    let damage_log_dir = app_paths().damage_log_dir(config.damage_log_dir.as_deref());
+    match command {
+        Command::Record { server, port, capture, numbers } => {
+            Runtime::new()?.block_on(record(server, port, capture, damage_log_dir, number_format(numbers)))
+        }
+        Command::Replay { capture, dry_run, numbers } => {
+            replay(&capture, (!dry_run).then_some(damage_log_dir), number_format(numbers))
+        }
+        Command::Summarize { files, format, top, numbers } => {
+            summarize(&files, format, top, number_format(numbers))
+        }
+    }
+}
+
+/// `PacketHandler` and its battle data without the GUI around them
+struct HeadlessAnalyzer {
+    packet_handler: PacketHandler,
+    battle: BattleData,
+    write_files: bool,
+    verbose: bool,
+    top_hits: usize,
+    in_battle: bool,
+}
+
+impl HeadlessAnalyzer {
+    /// Battle CSVs and summaries are written to `output_dir` if one is given
+    fn new(output_dir: Option<PathBuf>, verbose: bool) -> Self {
+        let write_files = output_dir.is_some();
+        let mut packet_handler = PacketHandler::new();
+        packet_handler.set_csv_enabled(write_files);
+        if let Some(output_dir) = output_dir {
+            packet_handler.set_output_dir(output_dir);
+        }
+
+        Self {
+            packet_handler,
+            battle: BattleData::default(),
+            write_files,
+            verbose,
+            top_hits: DEFAULT_TOP_HITS,
+            in_battle: false,
+        }
+    }
+
+    /// Returns a summary for every battle that ended with this packet
+    fn feed(&mut self, packet: Packet) -> anyhow::Result<Vec<BattleSummary>> {
+        let output = self.packet_handler.handle_packet(&mut self.battle, packet);
+
+        let mut summaries = Vec::new();
+        for event in output.events {
+            match event.kind {
+                BattleEventKind::Lineup { avatars } => {
+                    self.in_battle = true;
+                    if self.verbose {
+                        println!("{}", tr!("Battle started: {}", avatars.join(", ")));
+                    }
+                }
+                BattleEventKind::BattleEnd { .. } => {
+                    self.in_battle = false;
+                    summaries.push(self.finish_battle()?);
+                }
+                _ => {}
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// Summary of a battle that was cut off before `OnBattleEnd`
+    fn unfinished(&self) -> Option<BattleSummary> {
+        self.in_battle
+            .then(|| BattleSummary::from_battle(&self.battle, self.top_hits))
+    }
+
+    fn finish_battle(&self) -> anyhow::Result<BattleSummary> {
+        let summary = BattleSummary::from_battle(&self.battle, self.top_hits);
+
+        let csv_path = self.packet_handler.current_file();
+        if self.write_files && !csv_path.is_empty() {
+            let json_path = Path::new(csv_path).with_extension("json");
+            fs::write(&json_path, serde_json::to_string_pretty(&summary)?)
+                .with_context(|| format!("Failed to write {}", json_path.display()))?;
+            if self.verbose {
+                println!("{}", tr!("Wrote {} and {}", csv_path, json_path.display()));
+            }
+        }
+        Ok(summary)
+    }
+}
+
+async fn record(
+    server: String,
+    port: String,
+    capture_path: Option<PathBuf>,
+    damage_log_dir: PathBuf,
+    numbers: NumberFormat,
+) -> anyhow::Result<()> {
+    let capture_path = capture_path.unwrap_or_else(|| {
+        damage_log_dir.join(format!(
+            "HSR_{}.jsonl",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        ))
+    });
+    if let Some(parent) = capture_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut capture = CaptureWriter::create(&capture_path)
+        .with_context(|| format!("Failed to create {}", capture_path.display()))?;
+
+    let mut analyzer = HeadlessAnalyzer::new(Some(damage_log_dir), true);
+    let connected = Arc::new(Mutex::new(false));
+    let (payload_tx, mut payload_rx) = mpsc::channel(100);
+    tokio::spawn(client::run_client(
+        Arc::new(Mutex::new(server.clone())),
+        Arc::new(Mutex::new(port.clone())),
+        connected.clone(),
+        Arc::new(Mutex::new(MessageLogger::default())),
+        payload_tx,
+    ));
+
+    println!("{}", tr!("Recording to {}, press Ctrl+C to stop", capture.path().display()));
+    println!("{}", tr!("Connecting to {}:{}...", server, port));
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let mut status_interval = tokio::time::interval(Duration::from_secs(1));
+    let mut was_connected = false;
+
+    loop {
+        tokio::select! {
+            Some(packet) = payload_rx.recv() => {
+                capture.write(&packet)?;
+                for summary in analyzer.feed(packet)? {
+                    println!("{}", summary.display(numbers));
+                }
+            }
+            _ = status_interval.tick() => {
+                let is_connected = *connected.lock().await;
+                if is_connected != was_connected {
+                    println!("{}", if is_connected { tr("Connected") } else { tr("Disconnected, retrying...") });
+                    was_connected = is_connected;
+                }
+            }
+            _ = &mut ctrl_c => break,
+        }
+    }
+
+    if let Some(summary) = analyzer.unfinished() {
+        println!("{}\n{}", tr("Battle in progress when recording stopped:"), summary.display(numbers));
+    }
+    println!("{}", tr!("Capture saved to {}", capture.path().display()));
+    Ok(())
+}
+
+/// Writes battle files to `output_dir` unless it's a dry run
+fn replay(capture_path: &Path, output_dir: Option<PathBuf>, numbers: NumberFormat) -> anyhow::Result<()> {
+    let mut analyzer = HeadlessAnalyzer::new(output_dir, true);
+    for captured in capture::read_capture(capture_path)? {
+        for summary in analyzer.feed(captured.packet)? {
+            println!("{}", summary.display(numbers));
+        }
+    }
+    if let Some(summary) = analyzer.unfinished() {
+        println!("{}\n{}", tr("Capture ends mid-battle:"), summary.display(numbers));
+    }
+    Ok(())
+}
+
+fn summarize(paths: &[PathBuf], format: OutputFormat, top: usize, numbers: NumberFormat) -> anyhow::Result<()> {
+    let mut summaries = Vec::new();
+    for path in expand_paths(paths)? {
+        let battles = battle_summaries(&path, top)?;
+        summaries.extend(battles.into_iter().enumerate().map(|(i, summary)| FileSummary {
+            file: path.display().to_string(),
+            battle: i + 1,
+            summary,
+        }));
+    }
+
+    match format {
+        OutputFormat::Text => {
+            for summary in &summaries {
+                println!(
+                    "== {} ==\n{}",
+                    tr!("{} (battle {})", summary.file, summary.battle),
+                    summary.summary.display(numbers)
+                );
+            }
+        }
+        OutputFormat::Markdown => {
+            for summary in &summaries {
+                println!(
+                    "## {}\n\n{}",
+                    tr!("{} (battle {})", summary.file, summary.battle),
+                    summary.summary.to_markdown(numbers)
+                );
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summaries)?),
+    }
+    Ok(())
+}
+
+/// CSVs hold exactly one battle; captures may hold several, and may end mid-battle
+fn battle_summaries(path: &Path, top: usize) -> anyhow::Result<Vec<BattleSummary>> {
+    if has_extension(path, "csv") {
+        return Ok(vec![BattleSummary::from_csv(path, top)?]);
+    }
+
+    let mut analyzer = HeadlessAnalyzer::new(None, false);
+    analyzer.top_hits = top;
+    let mut battles = Vec::new();
+    for captured in capture::read_capture(path)? {
+        battles.extend(analyzer.feed(captured.packet)?);
+    }
+    battles.extend(analyzer.unfinished());
+    Ok(battles)
+}
+
+fn expand_paths(paths: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut expanded = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut csvs: Vec<PathBuf> = fs::read_dir(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| has_extension(path, "csv"))
+                .collect();
+            csvs.sort();
+            expanded.extend(csvs);
+        } else {
+            expanded.push(path.clone());
+        }
+    }
+    Ok(expanded)
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
@@ -1,7 +1,13 @@
-pub mod message_logger;
 pub mod models;
-pub mod packet_handler;
 pub mod helpers;
+#[cfg(windows)]
 pub mod launcher;
+pub mod platform;
 pub mod config;
-pub mod updater;
\ No newline at end of file
+pub mod updater;
+pub mod live_stats;
+pub mod client;
+pub mod file_logger;
+pub mod paths;
+pub mod preferences;
+pub mod layout;
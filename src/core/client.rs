@@ -0,0 +1,100 @@
+use std::{sync::Arc, time::Duration};
+
+use futures_util::FutureExt;
+use rust_socketio::{
+    asynchronous::{Client, ClientBuilder},
+    Event, Payload,
+};
+use tokio::{
+    sync::{mpsc, Mutex},
+    time::sleep,
+};
+
+use veritas_core::message_logger::{LogCategory, LogLevel, MessageLogger};
+
+use crate::core::models::Packet;
+
+/// Keeps a Socket.IO connection to the injected module alive and forwards
+/// every custom event it sends as a [`Packet`]. Logs each time the
+/// connection comes up or drops. Never returns.
+pub async fn run_client(
+    server_addr: Arc<Mutex<String>>,
+    server_port: Arc<Mutex<String>>,
+    connected: Arc<Mutex<bool>>,
+    message_logger: Arc<Mutex<MessageLogger>>,
+    payload_tx: mpsc::Sender<Packet>,
+) {
+    // This is so verbose, but necessary
+    loop {
+        if !*connected.lock().await {
+            let server = format!("{}:{}", server_addr.lock().await, server_port.lock().await);
+            let on_connected_status = connected.clone();
+            let on_disconnected_status = connected.clone();
+            let on_connected_logger = message_logger.clone();
+            let on_disconnected_logger = message_logger.clone();
+            let on_connected_server = server.clone();
+            let on_disconnected_server = server.clone();
+            let payload_tx = payload_tx.clone();
+
+            let connected_callback = move |_payload: Payload, _socket: Client| {
+                let on_connected_status = on_connected_status.clone();
+                let on_connected_logger = on_connected_logger.clone();
+                let on_connected_server = on_connected_server.clone();
+                async move {
+                    *on_connected_status.lock().await = true;
+                    on_connected_logger.lock().await.log(
+                        LogLevel::Info,
+                        LogCategory::Connection,
+                        format!("Connected to {}", on_connected_server),
+                    );
+                }
+                .boxed()
+            };
+
+            let disconnected_callback = move |_payload: Payload, _socket: Client| {
+                let on_disconnected_status = on_disconnected_status.clone();
+                let on_disconnected_logger = on_disconnected_logger.clone();
+                let on_disconnected_server = on_disconnected_server.clone();
+                async move {
+                    let mut status = on_disconnected_status.lock().await;
+                    // Failed attempts also error, only a dropped connection is worth a line
+                    if *status {
+                        on_disconnected_logger.lock().await.log(
+                            LogLevel::Warn,
+                            LogCategory::Connection,
+                            format!("Disconnected from {}", on_disconnected_server),
+                        );
+                    }
+                    *status = false;
+                }
+                .boxed()
+            };
+
+            let message_handler_callback  = move |event: Event, payload: Payload, _socket: Client| {
+                let payload_tx = payload_tx.clone();
+                async move {
+                    if let Event::Custom(e) = event {
+                        if let Payload::Text(text) = payload {
+                            for msg in text {
+                                let _ = payload_tx.send(Packet { r#type: e.clone(), data: msg }).await;
+                            }
+                        }
+                    }
+                }
+                .boxed()
+            };
+
+            ClientBuilder::new(format!("http://{}/", server))
+            .namespace("/")
+            .on(Event::Connect, connected_callback)
+            .on(Event::Error, disconnected_callback)
+            .on_any(message_handler_callback)
+            .reconnect(false)
+            .connect()
+            .await
+            .ok();
+        }
+
+        sleep(Duration::from_secs(1)).await;
+    }
+}
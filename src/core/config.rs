@@ -1,39 +1,379 @@
-use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use veritas_core::helpers::CharacterColors;
+use crate::core::layout::DockLayout;
+use crate::core::paths::app_paths;
+use crate::core::preferences::{Preferences, Session};
 use crate::core::updater::VeritasVersion;
 
-#[derive(Serialize, Deserialize, Default, Clone)]
+/// How long [`ConfigStore::preview`] edits must settle before they are saved
+const PREVIEW_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// Schema version written by this build, see [`MIGRATIONS`]
+pub const CONFIG_VERSION: u32 = 1;
+
+/// `MIGRATIONS[n]` upgrades a version `n` config to version `n + 1`
+const MIGRATIONS: &[fn(&mut serde_json::Map<String, Value>)] = &[
+    // 0 -> 1: configs from before versioning; the updater treated a missing
+    // `version_type` as Global Beta, so make that explicit
+    |config| {
+        if config.get("version_type").is_none_or(Value::is_null) {
+            config.insert("version_type".into(), serde_json::to_value(VeritasVersion::default()).unwrap());
+        }
+    },
+];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
 pub struct Config {
+    pub version: u32,
     pub game_path: Option<String>,
     pub dll_version: Option<String>,
-    pub version_type: Option<VeritasVersion>,
+    pub version_type: VeritasVersion,
+    pub live_stats_enabled: bool,
+    pub live_stats_port: Option<u16>,
+    /// Where battle CSVs go, `None` for the mode's default
+    pub damage_log_dir: Option<PathBuf>,
+    /// Filled in as new characters are seen, edited under Preferences → Charts
+    pub character_colors: CharacterColors,
+    /// Layouts saved by name from the View menu
+    pub layouts: BTreeMap<String, DockLayout>,
+    pub preferences: Preferences,
+    pub session: Session,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            game_path: None,
+            dll_version: None,
+            version_type: VeritasVersion::default(),
+            live_stats_enabled: false,
+            live_stats_port: None,
+            damage_log_dir: None,
+            character_colors: CharacterColors::default(),
+            layouts: BTreeMap::new(),
+            preferences: Preferences::default(),
+            session: Session::default(),
+        }
+    }
+}
+
+/// A `config.json` that exists but could not be read. The file is left alone
+/// (and copied to `backup_path`) until the user picks reset or repair.
+#[derive(Clone, Debug)]
+pub struct ConfigProblem {
+    pub path: PathBuf,
+    pub backup_path: Option<PathBuf>,
+    /// Dotted path of the offending field, `None` for JSON syntax errors
+    pub field: Option<String>,
+    pub error: String,
+    contents: String,
+}
+
+impl ConfigProblem {
+    /// Only well-formed JSON can be repaired field by field
+    pub fn is_repairable(&self) -> bool {
+        serde_json::from_str::<Value>(&self.contents).is_ok_and(|value| value.is_object())
+    }
 }
 
 impl Config {
-    pub fn load() -> Self {
-        if let Some(config_path) = get_config_path() {
-            if let Ok(contents) = fs::read_to_string(config_path) {
-                if let Ok(config) = serde_json::from_str(&contents) {
-                    return config;
+    fn load() -> (Self, Option<ConfigProblem>) {
+        let config_path = app_paths().config_path();
+        let Ok(contents) = fs::read_to_string(&config_path) else {
+            return Default::default();
+        };
+
+        match Self::parse(&contents) {
+            Ok((config, migrated)) => {
+                if migrated {
+                    log::info!("Migrated config to version {}", CONFIG_VERSION);
+                    config.save();
                 }
+                (config, None)
+            }
+            Err((field, error)) => {
+                log::error!(
+                    "Failed to read {:?} at {}: {}",
+                    config_path,
+                    field.as_deref().unwrap_or("<syntax>"),
+                    error
+                );
+                let backup_path = config_path.with_extension(format!(
+                    "json.{}.bak",
+                    chrono::Local::now().format("%Y%m%d_%H%M%S")
+                ));
+                let backup_path = match fs::copy(&config_path, &backup_path) {
+                    Ok(_) => Some(backup_path),
+                    Err(e) => {
+                        log::error!("Failed to back up unreadable config: {}", e);
+                        None
+                    }
+                };
+                let problem = ConfigProblem {
+                    path: config_path,
+                    backup_path,
+                    field,
+                    error,
+                    contents,
+                };
+                (Default::default(), Some(problem))
+            }
+        }
+    }
+
+    /// The saved config for callers that must not touch the file, like the CLI:
+    /// nothing is migrated on disk or backed up and an unreadable file reads as defaults
+    pub fn read_only() -> Self {
+        fs::read_to_string(app_paths().config_path())
+            .ok()
+            .and_then(|contents| Self::parse(&contents).ok())
+            .map(|(config, _)| config)
+            .unwrap_or_default()
+    }
+
+    /// Returns the config and whether it had to be migrated, or the failing field and error
+    fn parse(contents: &str) -> Result<(Self, bool), (Option<String>, String)> {
+        let mut value: Value = serde_json::from_str(contents).map_err(|e| (None, e.to_string()))?;
+        let migrated = migrate(&mut value);
+
+        serde_path_to_error::deserialize(value)
+            .map(|config| (config, migrated))
+            .map_err(|e| (Some(e.path().to_string()), e.inner().to_string()))
+    }
+
+    /// Keeps every field of `contents` that deserializes on its own and
+    /// defaults the rest, returning the dotted paths of the dropped fields.
+    /// Objects are salvaged field by field, so one bad leaf doesn't cost its siblings.
+    fn repair(contents: &str) -> Option<(Self, Vec<String>)> {
+        let mut value: Value = serde_json::from_str(contents).ok()?;
+        migrate(&mut value);
+        let Value::Object(fields) = value else {
+            return None;
+        };
+
+        let mut repaired = serde_json::to_value(Config::default()).ok()?;
+        let mut dropped = Vec::new();
+        for (key, field) in fields {
+            salvage(&mut repaired, &mut vec![key], field, &mut dropped);
+        }
+        let config = serde_json::from_value(repaired).ok()?;
+        Some((config, dropped))
+    }
+
+    fn save(&self) {
+        let config_path = app_paths().config_path();
+        if let Ok(contents) = serde_json::to_string_pretty(self) {
+            let _ = fs::create_dir_all(&app_paths().config_dir);
+            if let Err(e) = fs::write(&config_path, contents) {
+                log::error!("Failed to save {:?}: {}", config_path, e);
             }
         }
-        Default::default()
     }
+}
+
+/// Puts `field` at `path` in `repaired` if the result is still a valid [`Config`].
+/// Otherwise an object replacing one that is already there is tried key by key,
+/// and anything else is left at its previous value and its path added to `dropped`.
+fn salvage(repaired: &mut Value, path: &mut Vec<String>, field: Value, dropped: &mut Vec<String>) {
+    let (key, parent_path) = path.split_last().expect("salvage needs a non-empty path");
+    let parent_pointer: String = parent_path
+        .iter()
+        .map(|key| format!("/{}", key.replace('~', "~0").replace('/', "~1")))
+        .collect();
+    let Some(Value::Object(parent)) = repaired.pointer_mut(&parent_pointer) else {
+        return;
+    };
 
-    pub fn save(&self) {
-        if let Some(config_path) = get_config_path() {
-            if let Ok(contents) = serde_json::to_string_pretty(self) {
-                let _ = fs::create_dir_all(config_path.parent().unwrap());
-                let _ = fs::write(config_path, contents);
+    let previous = parent.insert(key.clone(), field.clone());
+    if serde_json::from_value::<Config>(repaired.clone()).is_ok() {
+        return;
+    }
+
+    let Some(Value::Object(parent)) = repaired.pointer_mut(&parent_pointer) else {
+        return;
+    };
+    match previous {
+        Some(previous) => parent.insert(key.clone(), previous),
+        None => parent.remove(key),
+    };
+    match (field, parent.get(key)) {
+        (Value::Object(fields), Some(Value::Object(_))) => {
+            for (key, field) in fields {
+                path.push(key);
+                salvage(repaired, path, field, dropped);
+                path.pop();
             }
         }
+        _ => dropped.push(path.join(".")),
     }
 }
 
-fn get_config_path() -> Option<PathBuf> {
-    ProjectDirs::from("com", "veritas", "veritas-app")
-        .map(|proj_dirs| proj_dirs.config_dir().join("config.json"))
+/// Runs the migrations a config older than [`CONFIG_VERSION`] needs, returning whether any ran
+fn migrate(value: &mut Value) -> bool {
+    let Value::Object(map) = value else {
+        return false;
+    };
+    let version = map.get("version").and_then(Value::as_u64).unwrap_or(0) as usize;
+    let mut migrated = false;
+    for migration in MIGRATIONS.iter().skip(version) {
+        migration(map);
+        migrated = true;
+    }
+    if migrated {
+        map.insert("version".into(), CONFIG_VERSION.into());
+    }
+    migrated
+}
+
+/// The one in-memory copy of [`Config`], shared by the app state and the updater.
+/// Every [`ConfigStore::update`] that changes something is saved and announced to subscribers.
+#[derive(Clone)]
+pub struct ConfigStore {
+    tx: Arc<watch::Sender<Config>>,
+    problem: Arc<Mutex<Option<ConfigProblem>>>,
+    /// When the last [`ConfigStore::preview`] not yet on disk was made
+    unsaved_since: Arc<Mutex<Option<Instant>>>,
+}
+
+impl ConfigStore {
+    pub fn load() -> Self {
+        let (config, problem) = Config::load();
+        let (tx, _) = watch::channel(config);
+        Self {
+            tx: Arc::new(tx),
+            problem: Arc::new(Mutex::new(problem)),
+            unsaved_since: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Set while an unreadable `config.json` is waiting on [`Self::reset`] or [`Self::repair`]
+    pub fn problem(&self) -> Option<ConfigProblem> {
+        self.problem.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Overwrites the unreadable file with the current in-memory settings
+    pub fn reset(&self) {
+        self.problem.lock().unwrap_or_else(|e| e.into_inner()).take();
+        self.tx.borrow().save();
+        log::info!("Replaced unreadable config with defaults");
+    }
+
+    /// Salvages what it can from the unreadable file, returning the fields that had to be dropped
+    pub fn repair(&self) -> Option<Vec<String>> {
+        let problem = self.problem()?;
+        let (config, dropped) = Config::repair(&problem.contents)?;
+        self.problem.lock().unwrap_or_else(|e| e.into_inner()).take();
+        self.tx.send_replace(config);
+        self.tx.borrow().save();
+        log::info!("Repaired config, dropped fields: {:?}", dropped);
+        Some(dropped)
+    }
+
+    pub fn read<T>(&self, f: impl FnOnce(&Config) -> T) -> T {
+        f(&self.tx.borrow())
+    }
+
+    /// Applies `f`, saving and notifying subscribers only if it changed anything.
+    /// Nothing is written while an unreadable file is still awaiting recovery.
+    pub fn update(&self, f: impl FnOnce(&mut Config)) {
+        let modified = self.tx.send_if_modified(|config| {
+            let before = config.clone();
+            f(config);
+            *config != before
+        });
+        if modified {
+            self.save();
+        }
+    }
+
+    /// Like [`Self::update`] but leaves saving to [`Self::save_settled`], for edits
+    /// such as slider drags that change the config every frame
+    pub fn preview(&self, f: impl FnOnce(&mut Config)) {
+        let modified = self.tx.send_if_modified(|config| {
+            let before = config.clone();
+            f(config);
+            *config != before
+        });
+        if modified {
+            *self.unsaved_since.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+        }
+    }
+
+    /// Saves previewed edits once none have been made for a moment, returning how
+    /// long to wait before calling again while some are still settling
+    pub fn save_settled(&self) -> Option<Duration> {
+        let unsaved_since = *self.unsaved_since.lock().unwrap_or_else(|e| e.into_inner());
+        let wait = PREVIEW_SAVE_DELAY.checked_sub(unsaved_since?.elapsed());
+        if wait.is_none() {
+            self.save();
+        }
+        wait
+    }
+
+    /// Saves previewed edits right away, such as when the app closes
+    pub fn save_pending(&self) {
+        if self.unsaved_since.lock().unwrap_or_else(|e| e.into_inner()).is_some() {
+            self.save();
+        }
+    }
+
+    /// Writes the config unless the file on disk is still waiting to be recovered
+    fn save(&self) {
+        self.unsaved_since.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if self.problem().is_none() {
+            self.tx.borrow().save();
+        }
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<Config> {
+        self.tx.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repair_drops_only_the_bad_nested_field() {
+        let contents = r#"{
+            "version": 1,
+            "game_path": "C:/Games/StarRail.exe",
+            "preferences": {
+                "compact_opacity": 0.5,
+                "chart_heights": { "dpav": "tall", "totals": 123.0 }
+            },
+            "live_stats_port": "not a port"
+        }"#;
+        let (config, dropped) = Config::repair(contents).unwrap();
+
+        assert_eq!(dropped, vec!["live_stats_port", "preferences.chart_heights.dpav"]);
+        assert_eq!(config.game_path.as_deref(), Some("C:/Games/StarRail.exe"));
+        assert_eq!(config.preferences.compact_opacity, 0.5);
+        assert_eq!(config.preferences.chart_heights.totals, 123.0);
+        assert_eq!(config.preferences.chart_heights.dpav, Preferences::default().chart_heights.dpav);
+    }
+
+    #[test]
+    fn repair_keeps_the_readable_layouts() {
+        let good = serde_json::to_value(DockLayout::default()).unwrap();
+        let contents = serde_json::json!({
+            "layouts": { "good": good, "bad": { "root": 5 } },
+        })
+        .to_string();
+        let (config, dropped) = Config::repair(&contents).unwrap();
+
+        assert_eq!(dropped, vec!["layouts.bad"]);
+        assert!(config.layouts.contains_key("good"));
+        assert!(!config.layouts.contains_key("bad"));
+    }
 }
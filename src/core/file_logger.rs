@@ -0,0 +1,128 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::core::paths::app_paths;
+
+const LOG_FILE_NAME: &str = "veritas.log";
+/// Size at which the current file is rotated out
+const MAX_FILE_SIZE: u64 = 2 * 1024 * 1024;
+/// Rotated files kept next to the current one, `veritas.1.log` being the newest
+const MAX_ROTATED_FILES: usize = 4;
+
+/// Directory holding `veritas.log` and its rotated predecessors
+pub fn log_dir() -> PathBuf {
+    app_paths().log_dir()
+}
+
+/// `log` backend appending to `veritas.log` and rotating it once it grows past [`MAX_FILE_SIZE`].
+/// Records from other crates are only kept at warning level and above.
+struct FileLogger {
+    dir: PathBuf,
+    file: Mutex<Option<File>>,
+}
+
+impl FileLogger {
+    fn path(&self) -> PathBuf {
+        self.dir.join(LOG_FILE_NAME)
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("veritas.{}.log", index))
+    }
+
+    fn open(&self) -> std::io::Result<File> {
+        fs::create_dir_all(&self.dir)?;
+        OpenOptions::new().create(true).append(true).open(self.path())
+    }
+
+    fn rotate(&self) -> std::io::Result<()> {
+        let _ = fs::remove_file(self.rotated_path(MAX_ROTATED_FILES));
+        for index in (1..MAX_ROTATED_FILES).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(from, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(self.path(), self.rotated_path(1))
+    }
+
+    fn write_line(&self, line: &str) -> std::io::Result<()> {
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+
+        if file.is_none() {
+            *file = Some(self.open()?);
+        }
+        if file.as_ref().and_then(|f| f.metadata().ok()).is_some_and(|m| m.len() >= MAX_FILE_SIZE) {
+            // Close the handle first, Windows won't rename an open file
+            *file = None;
+            self.rotate()?;
+            *file = Some(self.open()?);
+        }
+
+        match file.as_mut() {
+            Some(f) => writeln!(f, "{}", line),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let ours = metadata.target().starts_with("veritas");
+        metadata.level() <= log::max_level() && (ours || metadata.level() <= Level::Warn)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} [{}] {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        // Nowhere left to report a failing log file
+        let _ = self.write_line(&line);
+    }
+
+    fn flush(&self) {
+        if let Some(file) = self.file.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Changes the lowest level written from now on, e.g. when the preference changes
+pub fn set_level(level: LevelFilter) {
+    log::set_max_level(level);
+}
+
+/// Installs the file logger and a panic hook that records panics before the
+/// default hook runs. Only the first call has any effect.
+pub fn init(level: LevelFilter) {
+    let logger = FileLogger {
+        dir: log_dir(),
+        file: Mutex::new(None),
+    };
+    if log::set_boxed_logger(Box::new(logger)).is_err() {
+        return;
+    }
+    log::set_max_level(level);
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log::error!("{}", info);
+        log::logger().flush();
+        default_hook(info);
+    }));
+
+    log::info!("Veritas {} started", env!("CARGO_PKG_VERSION"));
+}
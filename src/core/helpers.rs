@@ -1,31 +1,99 @@
 use eframe::egui;
+use egui_plot::{Line, PlotPoints};
 
-pub fn format_damage(value: f64) -> String {
-    if value >= 1_000_000.0 {
-        let m = value / 1_000_000.0;
-        if m.fract() < 0.1 {
-            format!("{}M", m.floor())
-        } else {
-            format!("{:.1}M", (value / 1_000_000.0).floor() * 10.0 / 10.0)
+use crate::core::preferences::GraphMode;
+
+pub use veritas_core::helpers::{CharacterColors, Palette};
+
+pub fn get_character_color(colors: &CharacterColors, palette: Palette, name: &str) -> egui::Color32 {
+    let [r, g, b] = colors.rgb(palette, name);
+    egui::Color32::from_rgb(r, g, b)
+}
+
+/// Charts fill their pane, but never shrink below the configured height
+pub fn chart_height(ui: &egui::Ui, min_height: f32) -> f32 {
+    ui.available_height().max(min_height)
+}
+
+/// Turns per-point damage, one series per character, into what `mode` plots.
+/// `Share` gives each character's upper edge in a 0 to 100 stack, leaving out
+/// series that have already ended.
+pub fn graph_series(mut series: Vec<Vec<f64>>, mode: GraphMode) -> Vec<Vec<f64>> {
+    if mode == GraphMode::Damage {
+        return series;
+    }
+    for values in &mut series {
+        let mut total = 0.0;
+        for value in values.iter_mut() {
+            total += *value;
+            *value = total;
         }
-    } else if value >= 1_000.0 {
-        format!("{}K", (value / 1_000.0).floor())
-    } else {
-        format!("{}", value.floor())
     }
+    if mode == GraphMode::Share {
+        let len = series.iter().map(Vec::len).max().unwrap_or(0);
+        for i in 0..len {
+            let total: f64 = series.iter().filter_map(|values| values.get(i)).sum();
+            let mut stacked = 0.0;
+            for value in series.iter_mut().filter_map(|values| values.get_mut(i)) {
+                stacked += if total > 0.0 { *value / total * 100.0 } else { 0.0 };
+                *value = stacked;
+            }
+        }
+    }
+    series
 }
 
-pub fn get_character_color(index: usize) -> egui::Color32 {
-    const COLORS: &[egui::Color32] = &[
-        egui::Color32::from_rgb(255, 99, 132),   
-        egui::Color32::from_rgb(54, 162, 235),   
-        egui::Color32::from_rgb(255, 206, 86),   
-        egui::Color32::from_rgb(75, 192, 192),   
-        egui::Color32::from_rgb(153, 102, 255),  
-        egui::Color32::from_rgb(255, 159, 64),   
-        egui::Color32::from_rgb(231, 233, 237),  
-        egui::Color32::from_rgb(102, 255, 102),  
-    ];
-    
-    COLORS[index % COLORS.len()]
-}
\ No newline at end of file
+/// A character's line, filled down to the axis when stacked
+pub fn graph_line(points: Vec<[f64; 2]>, name: &str, color: egui::Color32, mode: GraphMode) -> Line<'static> {
+    let line = Line::new(PlotPoints::new(points)).name(name).color(color).width(2.0_f32);
+    match mode {
+        // Drawn top of the stack first so each area covers the rest of the one above
+        GraphMode::Share => line.fill(0.0_f32).fill_alpha(1.0_f32),
+        _ => line,
+    }
+}
+
+pub fn graph_axis_label(mode: GraphMode) -> &'static str {
+    match mode {
+        GraphMode::Share => "Share",
+        _ => "Damage",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damage_is_left_as_is() {
+        let series = vec![vec![1.0, 2.0], vec![3.0, 0.0]];
+        assert_eq!(graph_series(series.clone(), GraphMode::Damage), series);
+    }
+
+    #[test]
+    fn cumulative_keeps_a_running_total() {
+        let series = vec![vec![1.0, 2.0, 0.0, 4.0], vec![0.0, 5.0]];
+        assert_eq!(graph_series(series, GraphMode::Cumulative), [vec![1.0, 3.0, 3.0, 7.0], vec![0.0, 5.0]]);
+    }
+
+    #[test]
+    fn share_stacks_up_to_100() {
+        let series = vec![vec![1.0, 1.0], vec![3.0, 5.0]];
+        assert_eq!(graph_series(series, GraphMode::Share), [vec![25.0, 20.0], vec![100.0, 100.0]]);
+    }
+
+    #[test]
+    fn share_without_damage_is_zero() {
+        let series = vec![vec![0.0, 2.0], vec![0.0, 2.0]];
+        assert_eq!(graph_series(series, GraphMode::Share), [vec![0.0, 50.0], vec![0.0, 100.0]]);
+    }
+
+    #[test]
+    fn share_of_unequal_series_still_ends_at_100() {
+        let series = vec![vec![2.0], vec![2.0, 4.0, 0.0]];
+        assert_eq!(graph_series(series, GraphMode::Share), [vec![50.0], vec![100.0, 100.0, 100.0]]);
+
+        let series = vec![vec![], vec![1.0, 1.0], vec![3.0]];
+        assert_eq!(graph_series(series, GraphMode::Share), [vec![], vec![25.0, 100.0], vec![100.0]]);
+    }
+}
@@ -20,6 +20,21 @@ use windows::{
     },
 };
 
+use super::platform::GameLauncher;
+
+/// Injects through `CreateRemoteThread` + `LoadLibrary`
+pub struct WindowsLauncher;
+
+impl GameLauncher for WindowsLauncher {
+    fn inject_running(&self, process_name: &str, module_path: &str) {
+        hijack_process(process_name, module_path);
+    }
+
+    fn launch(&self, game_path: &str, module_path: &str) {
+        start_hijacked_process(game_path, module_path);
+    }
+}
+
 // https://stackoverflow.com/questions/865152/how-can-i-get-a-process-handle-by-its-name-in-c
 // https://github.com/3gstudent/Inject-dll-by-APC/blob/master/CreateRemoteThread.cpp
 fn enable_debug_priv() {
@@ -56,7 +71,7 @@ fn enable_debug_priv() {
         
                 CloseHandle(h_token).unwrap();        
             },
-            Err(e) => println!("{}", e)
+            Err(e) => log::error!("Failed to open process token: {}", e)
         }
     }
 }
@@ -136,7 +151,7 @@ pub fn inject_payload(process: HANDLE, module_path: &str) {
             },
             Err(_) => {
                 CloseHandle(process).unwrap();
-                println!("Failed to create remote thread");
+                log::error!("Failed to create remote thread");
             },
         }
     }
@@ -148,7 +163,7 @@ pub fn hijack_process(process_name: &str, module_path: &str) {
         Some(proc) => {
             inject_payload(proc, module_path);
         },
-        None => println!("Could not find {}", process_name),
+        None => log::warn!("Could not find {}", process_name),
     }
 }
 
@@ -190,7 +205,7 @@ pub fn start_hijacked_process(proc_path: &str, module_path: &str) {
 
         match result {
             Ok(_) => {
-                println!("Process created, waiting before injection...");
+                log::info!("Process created, waiting before injection...");
                 std::thread::sleep(std::time::Duration::from_millis(1000));
 
                 let process = Threading::OpenProcess(
@@ -211,7 +226,7 @@ pub fn start_hijacked_process(proc_path: &str, module_path: &str) {
                     PAGE_READWRITE
                 );
 
-                println!("Allocated memory for DLL path at {:?}", allocation);
+                log::debug!("Allocated memory for DLL path at {:?}", allocation);
 
                 WriteProcessMemory(
                     process,
@@ -224,7 +239,7 @@ pub fn start_hijacked_process(proc_path: &str, module_path: &str) {
                 let kernel32 = GetModuleHandleW(w!("kernel32")).unwrap();
                 let load_library = GetProcAddress(kernel32, s!("LoadLibraryA")).unwrap();
                 
-                println!("LoadLibraryA found at {:?}", load_library);
+                log::debug!("LoadLibraryA found at {:?}", load_library);
 
                 let thread = CreateRemoteThread(
                     process,
@@ -239,16 +254,16 @@ pub fn start_hijacked_process(proc_path: &str, module_path: &str) {
                 WaitForSingleObject(thread, INFINITE);
                 CloseHandle(thread);
 
-                println!("DLL injected, resuming process...");
+                log::info!("DLL injected, resuming process...");
                 ResumeThread(process_info.hThread);
 
                 CloseHandle(process);
                 CloseHandle(process_info.hThread);
                 CloseHandle(process_info.hProcess);
                 
-                println!("Game launch complete");
+                log::info!("Game launch complete");
             },
-            Err(e) => println!("Failed to create process: {}", e),
+            Err(e) => log::error!("Failed to create process: {}", e),
         }
     }
 }
\ No newline at end of file
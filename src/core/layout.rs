@@ -0,0 +1,444 @@
+use serde::{Deserialize, Serialize};
+
+/// Everything that can be docked in the main window
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Pane {
+    TurnDamage,
+    AvDamage,
+    Turns,
+    Heatmap,
+    Distribution,
+    Histogram,
+    Totals,
+    Stats,
+    Metrics,
+    Dpav,
+    Timeline,
+    Log,
+}
+
+impl Pane {
+    pub const ALL: [Pane; 12] = [
+        Pane::TurnDamage,
+        Pane::AvDamage,
+        Pane::Turns,
+        Pane::Heatmap,
+        Pane::Distribution,
+        Pane::Histogram,
+        Pane::Totals,
+        Pane::Stats,
+        Pane::Metrics,
+        Pane::Dpav,
+        Pane::Timeline,
+        Pane::Log,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Pane::TurnDamage => "Damage by Turn",
+            Pane::AvDamage => "Damage by Action Value",
+            Pane::Turns => "Turn Breakdown",
+            Pane::Heatmap => "Damage Heatmap",
+            Pane::Distribution => "Damage Distribution",
+            Pane::Histogram => "Hit Size Histogram",
+            Pane::Totals => "Total Damage by Character",
+            Pane::Stats => "Character Statistics",
+            Pane::Metrics => "Action Value Metrics",
+            Pane::Dpav => "DpAV over Time",
+            Pane::Timeline => "Action Timeline",
+            Pane::Log => "Logs",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// Side by side
+    Horizontal,
+    /// Stacked
+    Vertical,
+}
+
+/// Where a dragged pane lands relative to a tab group
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropZone {
+    Center,
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum DockNode {
+    Tabs {
+        panes: Vec<Pane>,
+        active: usize,
+    },
+    Split {
+        direction: SplitDirection,
+        /// Share of the space given to `first`
+        fraction: f32,
+        first: Box<DockNode>,
+        second: Box<DockNode>,
+    },
+}
+
+impl DockNode {
+    pub fn tabs(panes: impl Into<Vec<Pane>>) -> Self {
+        DockNode::Tabs { panes: panes.into(), active: 0 }
+    }
+
+    pub fn split(direction: SplitDirection, fraction: f32, first: DockNode, second: DockNode) -> Self {
+        DockNode::Split {
+            direction,
+            fraction,
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    fn contains(&self, pane: Pane) -> bool {
+        match self {
+            DockNode::Tabs { panes, .. } => panes.contains(&pane),
+            DockNode::Split { first, second, .. } => first.contains(pane) || second.contains(pane),
+        }
+    }
+
+    fn collect(&self, out: &mut Vec<Pane>) {
+        match self {
+            DockNode::Tabs { panes, .. } => out.extend(panes),
+            DockNode::Split { first, second, .. } => {
+                first.collect(out);
+                second.collect(out);
+            }
+        }
+    }
+
+    /// Removes `pane`, returning `None` if nothing is left of this node
+    fn remove(self, pane: Pane) -> Option<DockNode> {
+        match self {
+            DockNode::Tabs { mut panes, active } => {
+                let index = panes.iter().position(|p| *p == pane);
+                panes.retain(|p| *p != pane);
+                if panes.is_empty() {
+                    return None;
+                }
+                let active = match index {
+                    Some(index) if index < active => active - 1,
+                    _ => active.min(panes.len() - 1),
+                };
+                Some(DockNode::Tabs { panes, active })
+            }
+            DockNode::Split { direction, fraction, first, second } => {
+                match (first.remove(pane), second.remove(pane)) {
+                    (Some(first), Some(second)) => Some(DockNode::split(direction, fraction, first, second)),
+                    (Some(only), None) | (None, Some(only)) => Some(only),
+                    (None, None) => None,
+                }
+            }
+        }
+    }
+
+    /// Drops empty tab groups, collapsing the splits around them, and brings
+    /// `active` back in range, returning `None` if nothing is left
+    fn normalize(self) -> Option<DockNode> {
+        match self {
+            DockNode::Tabs { panes, .. } if panes.is_empty() => None,
+            DockNode::Tabs { panes, active } => {
+                let active = active.min(panes.len() - 1);
+                Some(DockNode::Tabs { panes, active })
+            }
+            DockNode::Split { direction, fraction, first, second } => {
+                match (first.normalize(), second.normalize()) {
+                    (Some(first), Some(second)) => Some(DockNode::split(direction, fraction, first, second)),
+                    (Some(only), None) | (None, Some(only)) => Some(only),
+                    (None, None) => None,
+                }
+            }
+        }
+    }
+
+    /// Finds the tab group holding `target` and docks `pane` into it
+    fn insert(&mut self, pane: Pane, target: Pane, zone: DropZone) -> bool {
+        match self {
+            DockNode::Tabs { panes, active } if panes.contains(&target) => {
+                if zone == DropZone::Center {
+                    panes.push(pane);
+                    *active = panes.len() - 1;
+                    return true;
+                }
+                let (direction, pane_first) = match zone {
+                    DropZone::Left => (SplitDirection::Horizontal, true),
+                    DropZone::Right => (SplitDirection::Horizontal, false),
+                    DropZone::Top => (SplitDirection::Vertical, true),
+                    _ => (SplitDirection::Vertical, false),
+                };
+                let existing = std::mem::replace(self, DockNode::tabs([]));
+                let new = DockNode::tabs([pane]);
+                *self = if pane_first {
+                    DockNode::split(direction, 0.5, new, existing)
+                } else {
+                    DockNode::split(direction, 0.5, existing, new)
+                };
+                true
+            }
+            DockNode::Tabs { .. } => false,
+            DockNode::Split { first, second, .. } => {
+                first.insert(pane, target, zone) || second.insert(pane, target, zone)
+            }
+        }
+    }
+
+    fn activate(&mut self, pane: Pane) {
+        match self {
+            DockNode::Tabs { panes, active } => {
+                if let Some(index) = panes.iter().position(|p| *p == pane) {
+                    *active = index;
+                }
+            }
+            DockNode::Split { first, second, .. } => {
+                first.activate(pane);
+                second.activate(pane);
+            }
+        }
+    }
+}
+
+/// A pane popped out into its own native window
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DetachedWindow {
+    pub pane: Pane,
+    /// Outer position, left to the window manager when `None`
+    pub position: Option<[f32; 2]>,
+    pub size: [f32; 2],
+}
+
+impl DetachedWindow {
+    const DEFAULT_SIZE: [f32; 2] = [640.0, 420.0];
+}
+
+/// Arrangement of the panes in the main window, `root` is `None` once every pane is closed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct DockLayout {
+    pub root: Option<DockNode>,
+    pub detached: Vec<DetachedWindow>,
+}
+
+impl Default for DockLayout {
+    /// Damage by turn and by AV above the distribution and totals, metrics and
+    /// DpAV down the right, close to the fixed layout from before docking
+    fn default() -> Self {
+        use SplitDirection::*;
+        let charts = DockNode::split(
+            Vertical,
+            0.5,
+            DockNode::tabs([Pane::TurnDamage, Pane::AvDamage, Pane::Turns, Pane::Heatmap]),
+            DockNode::split(
+                Horizontal,
+                0.5,
+                DockNode::tabs([Pane::Distribution, Pane::Histogram]),
+                DockNode::tabs([Pane::Totals, Pane::Stats]),
+            ),
+        );
+        let side = DockNode::split(
+            Vertical,
+            0.4,
+            DockNode::tabs([Pane::Metrics]),
+            DockNode::tabs([Pane::Dpav, Pane::Timeline, Pane::Log]),
+        );
+        Self {
+            root: Some(DockNode::split(Horizontal, 0.72, charts, side)),
+            detached: Vec::new(),
+        }
+    }
+}
+
+impl DockLayout {
+    /// Fixes up a layout read from the config, which may have been edited by hand
+    pub fn normalize(&mut self) {
+        self.root = self.root.take().and_then(DockNode::normalize);
+    }
+
+    pub fn contains(&self, pane: Pane) -> bool {
+        self.is_docked(pane) || self.is_detached(pane)
+    }
+
+    pub fn is_docked(&self, pane: Pane) -> bool {
+        self.root.as_ref().is_some_and(|root| root.contains(pane))
+    }
+
+    pub fn is_detached(&self, pane: Pane) -> bool {
+        self.detached.iter().any(|window| window.pane == pane)
+    }
+
+    /// Docked panes, in tree order
+    pub fn panes(&self) -> Vec<Pane> {
+        let mut panes = Vec::new();
+        if let Some(root) = &self.root {
+            root.collect(&mut panes);
+        }
+        panes
+    }
+
+    pub fn close(&mut self, pane: Pane) {
+        self.root = self.root.take().and_then(|root| root.remove(pane));
+        self.detached.retain(|window| window.pane != pane);
+    }
+
+    /// Moves `pane` out of the dock into its own window, opened where `previous`
+    /// says it last was or at the default size if it has not been out before
+    pub fn detach(&mut self, pane: Pane, previous: Option<DetachedWindow>) {
+        if self.is_detached(pane) {
+            return;
+        }
+        self.close(pane);
+        self.detached.push(previous.unwrap_or(DetachedWindow {
+            pane,
+            position: None,
+            size: DetachedWindow::DEFAULT_SIZE,
+        }));
+    }
+
+    /// Puts a detached `pane` back in the dock
+    pub fn attach(&mut self, pane: Pane) {
+        self.detached.retain(|window| window.pane != pane);
+        self.open(pane);
+    }
+
+    /// Adds `pane` as a tab beside the first docked pane, or as the only one
+    pub fn open(&mut self, pane: Pane) {
+        if self.contains(pane) {
+            return;
+        }
+        match self.panes().first() {
+            Some(&target) => self.dock(pane, target, DropZone::Center),
+            None => self.root = Some(DockNode::tabs([pane])),
+        }
+    }
+
+    /// Moves `pane` into the tab group holding `target`
+    pub fn dock(&mut self, pane: Pane, target: Pane, zone: DropZone) {
+        if pane == target && zone == DropZone::Center {
+            return;
+        }
+        let previous = self.clone();
+        self.root = self.root.take().and_then(|root| root.remove(pane));
+        let docked = match &mut self.root {
+            Some(root) => root.insert(pane, target, zone),
+            None => false,
+        };
+        // Dropping the only pane of a group onto its own edge leaves nothing to dock against
+        if !docked {
+            *self = previous;
+        }
+    }
+
+    pub fn activate(&mut self, pane: Pane) {
+        if let Some(root) = &mut self.root {
+            root.activate(pane);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Pane::*;
+    use SplitDirection::*;
+
+    fn tabs(panes: &[Pane], active: usize) -> DockNode {
+        DockNode::Tabs { panes: panes.to_vec(), active }
+    }
+
+    fn docked(root: DockNode) -> DockLayout {
+        DockLayout { root: Some(root), detached: Vec::new() }
+    }
+
+    #[test]
+    fn remove_keeps_the_same_tab_active() {
+        let node = tabs(&[Log, Dpav, Metrics], 2);
+        assert_eq!(node.remove(Log), Some(tabs(&[Dpav, Metrics], 1)));
+
+        let node = tabs(&[Log, Dpav, Metrics], 0);
+        assert_eq!(node.remove(Metrics), Some(tabs(&[Log, Dpav], 0)));
+    }
+
+    #[test]
+    fn remove_moves_off_a_removed_last_tab() {
+        let node = tabs(&[Log, Dpav, Metrics], 2);
+        assert_eq!(node.remove(Metrics), Some(tabs(&[Log, Dpav], 1)));
+    }
+
+    #[test]
+    fn remove_collapses_a_split_with_an_empty_side() {
+        let node = DockNode::split(Horizontal, 0.3, tabs(&[Log], 0), tabs(&[Dpav, Metrics], 1));
+        assert_eq!(node.clone().remove(Log), Some(tabs(&[Dpav, Metrics], 1)));
+        let emptied = node.remove(Log).and_then(|node| node.remove(Dpav)).and_then(|node| node.remove(Metrics));
+        assert_eq!(emptied, None);
+    }
+
+    #[test]
+    fn insert_in_the_center_adds_an_active_tab() {
+        let mut node = tabs(&[Log, Dpav], 0);
+        assert!(node.insert(Metrics, Dpav, DropZone::Center));
+        assert_eq!(node, tabs(&[Log, Dpav, Metrics], 2));
+    }
+
+    #[test]
+    fn insert_on_an_edge_splits_the_group() {
+        let mut node = tabs(&[Log], 0);
+        assert!(node.insert(Dpav, Log, DropZone::Left));
+        assert_eq!(node, DockNode::split(Horizontal, 0.5, tabs(&[Dpav], 0), tabs(&[Log], 0)));
+
+        let mut node = tabs(&[Log], 0);
+        assert!(node.insert(Dpav, Log, DropZone::Bottom));
+        assert_eq!(node, DockNode::split(Vertical, 0.5, tabs(&[Log], 0), tabs(&[Dpav], 0)));
+
+        assert!(!tabs(&[Log], 0).insert(Dpav, Metrics, DropZone::Left));
+    }
+
+    #[test]
+    fn dock_moves_a_pane_between_groups() {
+        let mut layout = docked(DockNode::split(Horizontal, 0.5, tabs(&[Log], 0), tabs(&[Dpav], 0)));
+        layout.dock(Log, Dpav, DropZone::Center);
+        assert_eq!(layout.root, Some(tabs(&[Dpav, Log], 1)));
+    }
+
+    #[test]
+    fn dock_onto_its_own_only_group_changes_nothing() {
+        let root = DockNode::split(Horizontal, 0.5, tabs(&[Log], 0), tabs(&[Dpav], 0));
+        let mut layout = docked(root.clone());
+        layout.dock(Log, Log, DropZone::Left);
+        assert_eq!(layout.root, Some(root.clone()));
+        layout.dock(Log, Log, DropZone::Center);
+        assert_eq!(layout.root, Some(root));
+    }
+
+    #[test]
+    fn normalize_drops_empty_groups_and_clamps_active() {
+        let json = r#"{"root":{"Tabs":{"panes":[],"active":0}}}"#;
+        let mut layout: DockLayout = serde_json::from_str(json).unwrap();
+        layout.normalize();
+        assert_eq!(layout.root, None);
+
+        let mut layout = docked(DockNode::split(Vertical, 0.5, tabs(&[], 3), tabs(&[Log, Dpav], 7)));
+        layout.normalize();
+        assert_eq!(layout.root, Some(tabs(&[Log, Dpav], 1)));
+    }
+
+    #[test]
+    fn detach_reopens_where_the_window_last_was() {
+        let previous = DetachedWindow { pane: Dpav, position: Some([40.0, 60.0]), size: [800.0, 500.0] };
+        let mut layout = docked(tabs(&[Log, Dpav], 1));
+        layout.detach(Dpav, Some(previous.clone()));
+        assert_eq!(layout.root, Some(tabs(&[Log], 0)));
+        assert_eq!(layout.detached, vec![previous]);
+
+        let mut layout = docked(tabs(&[Log, Dpav], 1));
+        layout.detach(Dpav, None);
+        assert_eq!(layout.detached[0].position, None);
+        assert_eq!(layout.detached[0].size, DetachedWindow::DEFAULT_SIZE);
+    }
+}
@@ -0,0 +1,248 @@
+use std::{collections::BTreeMap, convert::Infallible, net::TcpListener, sync::Arc};
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    http::header,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse,
+    },
+    routing::get,
+    Json, Router,
+};
+use futures_util::{stream, Stream};
+use tokio::{
+    sync::{broadcast, watch},
+    task::JoinHandle,
+};
+
+use veritas_core::{
+    helpers::{self, CharacterColors, NumberFormat, Palette},
+    i18n::{self, tr},
+    models::BattleEvent,
+    snapshot::BattleSnapshot,
+};
+
+pub const DEFAULT_LIVE_STATS_PORT: u16 = 1306;
+
+const OVERLAY_PAGE: &str = include_str!("../assets/overlay.html");
+
+/// Local HTTP server exposing the current battle for stream overlays.
+///
+/// `GET /api/battle` returns the latest [`BattleSnapshot`] and
+/// `GET /api/battle/stream` pushes a new one as a Server-Sent Event
+/// whenever the packet worker publishes an update. `GET /overlay` serves
+/// a browser-source page built on top of the stream, and `/ws/events`
+/// rebroadcasts every [`BattleEvent`] over a WebSocket. `GET /api/palette`
+/// and `GET /api/colors` give the selected palette and each known character's color.
+pub struct LiveStatsServer {
+    snapshot_tx: Arc<watch::Sender<BattleSnapshot>>,
+    event_tx: broadcast::Sender<BattleEvent>,
+    number_format_tx: watch::Sender<NumberFormat>,
+    colors_tx: watch::Sender<(Palette, CharacterColors)>,
+    handle: Option<JoinHandle<()>>,
+    port: Option<u16>,
+}
+
+#[derive(Clone)]
+struct ServerState {
+    snapshot_rx: watch::Receiver<BattleSnapshot>,
+    event_tx: broadcast::Sender<BattleEvent>,
+    number_format_rx: watch::Receiver<NumberFormat>,
+    colors_rx: watch::Receiver<(Palette, CharacterColors)>,
+}
+
+impl LiveStatsServer {
+    pub fn new() -> Self {
+        let (snapshot_tx, _) = watch::channel(BattleSnapshot::default());
+        let (event_tx, _) = broadcast::channel(256);
+        let (number_format_tx, _) = watch::channel(NumberFormat::default());
+        let (colors_tx, _) = watch::channel(Default::default());
+        Self {
+            snapshot_tx: Arc::new(snapshot_tx),
+            event_tx,
+            number_format_tx,
+            colors_tx,
+            handle: None,
+            port: None,
+        }
+    }
+
+    pub fn publisher(&self) -> Arc<watch::Sender<BattleSnapshot>> {
+        self.snapshot_tx.clone()
+    }
+
+    pub fn event_sender(&self) -> broadcast::Sender<BattleEvent> {
+        self.event_tx.clone()
+    }
+
+    /// Default number format of the overlay page, `?format=` overrides it per browser source
+    pub fn set_number_format(&self, number_format: NumberFormat) {
+        self.number_format_tx.send_if_modified(|current| {
+            let changed = *current != number_format;
+            *current = number_format;
+            changed
+        });
+    }
+
+    /// Colors the overlay and `/api/colors` hand out, matching the desktop charts
+    pub fn set_colors(&self, palette: Palette, characters: &CharacterColors) {
+        self.colors_tx.send_if_modified(|current| {
+            let changed = current.0 != palette || current.1 != *characters;
+            *current = (palette, characters.clone());
+            changed
+        });
+    }
+
+    pub fn running_port(&self) -> Option<u16> {
+        self.handle
+            .as_ref()
+            .filter(|handle| !handle.is_finished())
+            .and(self.port)
+    }
+
+    /// Binds to `127.0.0.1:port` and serves on `runtime`.
+    /// Binding happens synchronously so the caller can report a taken port.
+    pub fn start(&mut self, runtime: &tokio::runtime::Runtime, port: u16) -> std::io::Result<()> {
+        self.stop();
+
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        listener.set_nonblocking(true)?;
+
+        let router = Router::new()
+            .route("/api/battle", get(get_battle))
+            .route("/api/battle/stream", get(stream_battle))
+            .route("/api/palette", get(get_palette))
+            .route("/api/colors", get(get_colors))
+            .route("/overlay", get(get_overlay))
+            .route("/ws/events", get(events_socket))
+            .with_state(ServerState {
+                snapshot_rx: self.snapshot_tx.subscribe(),
+                event_tx: self.event_tx.clone(),
+                number_format_rx: self.number_format_tx.subscribe(),
+                colors_rx: self.colors_tx.subscribe(),
+            });
+
+        self.handle = Some(runtime.spawn(async move {
+            match tokio::net::TcpListener::from_std(listener) {
+                Ok(listener) => {
+                    if let Err(e) = axum::serve(listener, router).await {
+                        log::error!("Live stats server stopped: {}", e);
+                    }
+                }
+                Err(e) => log::error!("Failed to start live stats server: {}", e),
+            }
+        }));
+        self.port = Some(port);
+
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+        self.port = None;
+    }
+}
+
+async fn get_battle(State(state): State<ServerState>) -> impl IntoResponse {
+    let snapshot = state.snapshot_rx.borrow().clone();
+    ([(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")], Json(snapshot))
+}
+
+async fn stream_battle(State(state): State<ServerState>) -> impl IntoResponse {
+    (
+        [(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")],
+        Sse::new(snapshot_events(state.snapshot_rx)).keep_alive(KeepAlive::default()),
+    )
+}
+
+async fn events_socket(ws: WebSocketUpgrade, State(state): State<ServerState>) -> impl IntoResponse {
+    let event_rx = state.event_tx.subscribe();
+    ws.on_upgrade(move |socket| forward_events(socket, event_rx))
+}
+
+async fn forward_events(mut socket: WebSocket, mut event_rx: broadcast::Receiver<BattleEvent>) {
+    loop {
+        tokio::select! {
+            event = event_rx.recv() => match event {
+                Ok(event) => {
+                    let Ok(text) = serde_json::to_string(&event) else {
+                        continue;
+                    };
+                    if socket.send(Message::Text(text.into())).await.is_err() {
+                        break;
+                    }
+                }
+                // A slow subscriber just misses the events it fell behind on
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                _ => {}
+            },
+        }
+    }
+}
+
+async fn get_palette(State(state): State<ServerState>) -> impl IntoResponse {
+    ([(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")], Json(palette_hex(&state)))
+}
+
+async fn get_colors(State(state): State<ServerState>) -> impl IntoResponse {
+    ([(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")], Json(character_colors_hex(&state)))
+}
+
+async fn get_overlay(State(state): State<ServerState>) -> Html<String> {
+    // Inline the colors, number format and labels so the overlay matches the desktop charts without an extra request
+    let palette = serde_json::to_string(&palette_hex(&state)).unwrap_or_else(|_| "[]".to_string());
+    let colors = serde_json::to_string(&character_colors_hex(&state)).unwrap_or_else(|_| "{}".to_string());
+    let number_format = state.number_format_rx.borrow().key();
+    Html(
+        OVERLAY_PAGE
+            .replace("__VERITAS_PALETTE__", &palette)
+            .replace("__VERITAS_CHARACTER_COLORS__", &colors)
+            .replace("__VERITAS_NUMBER_FORMAT__", number_format)
+            .replace("__VERITAS_LANGUAGE__", i18n::language().key())
+            .replace("__VERITAS_T_TOTAL__", tr("Total"))
+            .replace("__VERITAS_T_DPAV__", tr("DpAV"))
+            .replace("__VERITAS_T_AV__", tr("AV"))
+            .replace("__VERITAS_T_CYCLE__", tr("Cycle")),
+    )
+}
+
+fn palette_hex(state: &ServerState) -> Vec<String> {
+    let palette = state.colors_rx.borrow().0;
+    palette.colors().iter().copied().map(helpers::hex).collect()
+}
+
+fn character_colors_hex(state: &ServerState) -> BTreeMap<String, String> {
+    let colors = state.colors_rx.borrow();
+    let (palette, characters) = &*colors;
+    characters
+        .0
+        .keys()
+        .map(|name| (name.clone(), characters.hex(*palette, name)))
+        .collect()
+}
+
+fn snapshot_events(
+    mut snapshot_rx: watch::Receiver<BattleSnapshot>,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    // Send the current state straight away so a freshly loaded overlay isn't blank
+    snapshot_rx.mark_changed();
+    stream::unfold(snapshot_rx, |mut snapshot_rx| async move {
+        snapshot_rx.changed().await.ok()?;
+        let snapshot = snapshot_rx.borrow_and_update().clone();
+        let event = Event::default()
+            .event("battle")
+            .json_data(&snapshot)
+            .unwrap_or_default();
+        Some((Ok(event), snapshot_rx))
+    })
+}
@@ -0,0 +1,75 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use directories::ProjectDirs;
+
+/// A file with this name beside the executable switches to portable mode
+pub const PORTABLE_MARKER: &str = "veritas.portable";
+
+/// Where config, the DLL, logs and battle data live.
+///
+/// Installed copies use the per-user directories from `ProjectDirs`, portable
+/// ones keep everything in the executable's folder.
+pub struct AppPaths {
+    pub portable: bool,
+    pub config_dir: PathBuf,
+    pub data_dir: PathBuf,
+    default_damage_log_dir: PathBuf,
+}
+
+impl AppPaths {
+    fn detect() -> Self {
+        let exe_dir = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf));
+
+        if let Some(exe_dir) = exe_dir.filter(|dir| dir.join(PORTABLE_MARKER).exists()) {
+            return Self {
+                portable: true,
+                config_dir: exe_dir.clone(),
+                data_dir: exe_dir.clone(),
+                default_damage_log_dir: exe_dir.join("damage_logs"),
+            };
+        }
+
+        match ProjectDirs::from("com", "veritas", "veritas-app") {
+            Some(proj_dirs) => Self {
+                portable: false,
+                config_dir: proj_dirs.config_dir().to_path_buf(),
+                data_dir: proj_dirs.data_dir().to_path_buf(),
+                // Installed copies have always written CSVs relative to the working directory
+                default_damage_log_dir: PathBuf::from("damage_logs"),
+            },
+            None => Self {
+                portable: false,
+                config_dir: PathBuf::from("."),
+                data_dir: PathBuf::from("."),
+                default_damage_log_dir: PathBuf::from("damage_logs"),
+            },
+        }
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.config_dir.join("config.json")
+    }
+
+    pub fn dll_path(&self) -> PathBuf {
+        self.data_dir.join("veritas.dll")
+    }
+
+    pub fn log_dir(&self) -> PathBuf {
+        self.data_dir.join("logs")
+    }
+
+    /// `configured` is the user's `damage_log_dir` setting, if any
+    pub fn damage_log_dir(&self, configured: Option<&Path>) -> PathBuf {
+        configured
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| self.default_damage_log_dir.clone())
+    }
+}
+
+pub fn app_paths() -> &'static AppPaths {
+    static PATHS: OnceLock<AppPaths> = OnceLock::new();
+    PATHS.get_or_init(AppPaths::detect)
+}
@@ -0,0 +1,23 @@
+/// Shown in place of launcher actions on platforms without a [`GameLauncher`]
+pub const LAUNCHER_UNAVAILABLE: &str = "Launching and injecting is only available on Windows";
+
+/// Starts the game or an already running client with the Veritas module loaded.
+pub trait GameLauncher {
+    /// Injects `module_path` into the running process whose name starts with `process_name`
+    fn inject_running(&self, process_name: &str, module_path: &str);
+
+    /// Starts the game at `game_path` suspended, injects `module_path` and resumes it
+    fn launch(&self, game_path: &str, module_path: &str);
+}
+
+/// The launcher for the current platform. Everywhere but Windows the
+/// analyzer can still connect to a game running on another machine.
+#[cfg(windows)]
+pub fn game_launcher() -> Option<&'static dyn GameLauncher> {
+    Some(&super::launcher::WindowsLauncher)
+}
+
+#[cfg(not(windows))]
+pub fn game_launcher() -> Option<&'static dyn GameLauncher> {
+    None
+}
@@ -0,0 +1,138 @@
+use eframe::egui::ThemePreference;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use veritas_core::helpers::{NumberFormat, Palette};
+use veritas_core::i18n::Language;
+use veritas_core::message_logger::LogLevel;
+
+use crate::core::layout::{DetachedWindow, DockLayout, Pane};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Unit {
+    #[default]
+    Turn,
+    ActionValue,
+}
+
+/// What the damage over time chart plots against its unit
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum GraphMode {
+    #[default]
+    Damage,
+    Cumulative,
+    /// Each character's share of the damage so far, stacked
+    Share,
+}
+
+impl GraphMode {
+    pub const ALL: [GraphMode; 3] = [GraphMode::Damage, GraphMode::Cumulative, GraphMode::Share];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GraphMode::Damage => "Damage",
+            GraphMode::Cumulative => "Cumulative",
+            GraphMode::Share => "Share over Time",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::System => "System",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+        }
+    }
+
+    pub fn to_egui(self) -> ThemePreference {
+        match self {
+            Theme::System => ThemePreference::System,
+            Theme::Light => ThemePreference::Light,
+            Theme::Dark => ThemePreference::Dark,
+        }
+    }
+}
+
+/// Smallest height of each chart; docked charts grow to fill their pane
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct ChartHeights {
+    pub damage_over_time: f32,
+    pub distribution: f32,
+    pub totals: f32,
+    pub dpav: f32,
+    pub timeline: f32,
+    pub histogram: f32,
+}
+
+impl Default for ChartHeights {
+    fn default() -> Self {
+        Self {
+            damage_over_time: 250.0,
+            distribution: 300.0,
+            totals: 300.0,
+            dpav: 200.0,
+            timeline: 200.0,
+            histogram: 250.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Preferences {
+    pub language: Language,
+    pub theme: Theme,
+    /// Damage plot brought to the front, and the heatmap's unit, at startup
+    /// unless the last session is restored
+    pub default_graph_unit: Unit,
+    pub number_format: NumberFormat,
+    pub chart_heights: ChartHeights,
+    pub palette: Palette,
+    /// Background opacity of the compact strip, 1.0 is opaque
+    pub compact_opacity: f32,
+    /// Lowest level written to the log file and shown in the log panel by default
+    pub log_level: LogLevel,
+    /// Reopen with the layout, pin and graph options of the last session
+    pub restore_session: bool,
+    pub check_updates_on_startup: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            theme: Theme::default(),
+            default_graph_unit: Unit::default(),
+            number_format: NumberFormat::default(),
+            chart_heights: ChartHeights::default(),
+            palette: Palette::default(),
+            compact_opacity: 1.0,
+            log_level: LogLevel::Info,
+            restore_session: true,
+            check_updates_on_startup: true,
+        }
+    }
+}
+
+/// UI state remembered between launches when [`Preferences::restore_session`] is on
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Session {
+    pub layout: DockLayout,
+    pub window_pinned: bool,
+    pub graph_mode: GraphMode,
+    /// Where each pane's window last was, so it pops out there again
+    pub window_geometry: BTreeMap<Pane, DetachedWindow>,
+}
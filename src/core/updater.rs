@@ -1,4 +1,3 @@
-use directories::ProjectDirs;
 use reqwest;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -8,6 +7,9 @@ use tokio::io::AsyncWriteExt;
 use self_update;
 use tempfile;
 
+use crate::core::config::ConfigStore;
+use crate::core::paths::app_paths;
+
 #[derive(Clone, Debug, Deserialize)]
 struct GithubRelease {
     tag_name: String,
@@ -20,89 +22,54 @@ struct GithubAsset {
     browser_download_url: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
-struct Config {
-    dll_version: Option<String>,
-    version_type: Option<VeritasVersion>,
-}
-
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum VeritasVersion {
+    #[default]
     GlobalBeta,
     CnBeta,
     GlobalProd,
 }
 
-impl Default for VeritasVersion {
-    fn default() -> Self {
-        Self::GlobalBeta
-    }
-}
-
 #[derive(Clone)]
 pub struct Updater {
     client: reqwest::Client,
-    app_version: String,
-    project_dirs: ProjectDirs,
     app_release: Option<GithubRelease>,
     dll_release: Option<GithubRelease>,
-    pub current_version_type: VeritasVersion,
+    config: ConfigStore,
     checked_dll_version: Option<String>,
 }
 
 impl Updater {
-    pub fn new() -> Self {
-        let project_dirs = ProjectDirs::from("com", "veritas", "veritas-app")
-            .expect("Failed to get project directories");
-
-        let version_type = if let Some(config_path) = get_config_path() {
-            if let Ok(contents) = fs::read_to_string(config_path) {
-                if let Ok(config) = serde_json::from_str::<Config>(&contents) {
-                    config.version_type.unwrap_or_default()
-                } else {
-                    VeritasVersion::default()
-                }
-            } else {
-                VeritasVersion::default()
-            }
-        } else {
-            VeritasVersion::default()
-        };
-            
+    pub fn new(config: ConfigStore) -> Self {
         Self {
             client: reqwest::Client::builder()
                 .user_agent("veritas-app")
                 .build()
                 .unwrap(),
-            app_version: env!("CARGO_PKG_VERSION").to_string(),
-            project_dirs,
             app_release: None,
             dll_release: None,
-            current_version_type: version_type,
+            config,
             checked_dll_version: None,
         }
     }
 
     pub fn get_dll_path(&self) -> PathBuf {
-        self.project_dirs.data_dir().join("veritas.dll")
+        app_paths().dll_path()
     }
 
     pub async fn check_app_update(&mut self) -> Option<String> {
-        match self.client
+        if let Ok(response) = self.client
             .get("https://api.github.com/repos/NightKoneko/veritas-app/releases/latest")
             .send()
-            .await 
+            .await
         {
-            Ok(response) => {
-                if let Ok(release) = response.json::<GithubRelease>().await {
-                    self.app_release = Some(release.clone());
-                    
-                    if release.tag_name != self_update::cargo_crate_version!() {
-                        return Some(release.tag_name);
-                    }
+            if let Ok(release) = response.json::<GithubRelease>().await {
+                self.app_release = Some(release.clone());
+                
+                if release.tag_name != self_update::cargo_crate_version!() {
+                    return Some(release.tag_name);
                 }
             }
-            Err(_) => {}
         }
         None
     }
@@ -118,12 +85,12 @@ impl Updater {
             .await
             .ok()?;
 
-        println!("Found release: {}", response.tag_name);
+        log::info!("Found release: {}", response.tag_name);
         
         let asset = response.assets.iter()
             .find(|a| a.name.ends_with(".exe"))?;
 
-        println!("Using asset: {}", asset.name);
+        log::info!("Using asset: {}", asset.name);
 
         let tmp_dir = tempfile::Builder::new()
             .prefix("veritas_update")
@@ -144,10 +111,10 @@ impl Updater {
 
         let current_exe = std::env::current_exe().ok()?;
         let new_exe = current_exe.with_extension("new");
-        println!("Moving update to: {:?}", new_exe);
+        log::info!("Moving update to: {:?}", new_exe);
         
         fs::copy(exe_path, &new_exe).ok()?;
-        println!("Update ready at: {:?}", new_exe);
+        log::info!("Update ready at: {:?}", new_exe);
 
         Some(())
     }
@@ -161,7 +128,7 @@ impl Updater {
             
         let releases = response.json::<Vec<GithubRelease>>().await.ok()?;
         
-        let version_filter = match self.current_version_type {
+        let version_filter = match self.config.read(|config| config.version_type.clone()) {
             VeritasVersion::GlobalBeta => "global-beta",
             VeritasVersion::CnBeta => "cn-beta",
             VeritasVersion::GlobalProd => "global-prod",
@@ -180,7 +147,7 @@ impl Updater {
         let release = self.dll_release.clone()
             .ok_or("No release info available, please check for updates first")?;
 
-        fs::create_dir_all(self.project_dirs.data_dir())?;
+        fs::create_dir_all(&app_paths().data_dir)?;
 
         let dll_asset = release.assets
             .iter()
@@ -197,6 +164,11 @@ impl Updater {
         let mut file = TokioFile::create(self.get_dll_path()).await?;
         file.write_all(&dll_bytes).await?;
 
+        // Saving the config writes to disk, so keep it off the runtime's worker threads
+        let config = self.config.clone();
+        tokio::task::spawn_blocking(move || config.update(|config| config.dll_version = Some(release.tag_name)))
+            .await?;
+
         Ok(())
     }
 
@@ -209,17 +181,6 @@ impl Updater {
     }
 
     pub fn current_dll_version(&self) -> Option<String> {
-        ProjectDirs::from("com", "veritas", "veritas-app")
-            .and_then(|proj_dirs| {
-                let config_path = proj_dirs.config_dir().join("config.json");
-                fs::read_to_string(config_path).ok()
-            })
-            .and_then(|contents| serde_json::from_str::<Config>(&contents).ok())
-            .and_then(|config| config.dll_version)
+        self.config.read(|config| config.dll_version.clone())
     }
 }
-
-fn get_config_path() -> Option<PathBuf> {
-    ProjectDirs::from("com", "veritas", "veritas-app")
-        .map(|proj_dirs| proj_dirs.config_dir().join("config.json"))
-}
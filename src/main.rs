@@ -6,20 +6,40 @@
     windows_subsystem = "windows"
 )]
 mod app;
+mod cli;
 mod core;
 mod widgets;
 mod panels;
 
+use clap::Parser;
 use eframe::egui::{self, IconData};
-use core::launcher::start_hijacked_process;
 use std::sync::Arc;
 
 fn main() -> eframe::Result<()> {
+    core::file_logger::init(if cfg!(debug_assertions) {
+        log::LevelFilter::Debug
+    } else {
+        log::LevelFilter::Info
+    });
+
+    // Attach before parsing so --help, --version and usage errors reach the terminal too
+    if std::env::args_os().len() > 1 {
+        attach_console();
+    }
+    if let Some(command) = cli::Cli::parse().command {
+        if let Err(e) = cli::run(command) {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1200.0, 800.0])
-            .with_min_inner_size([800.0, 600.0])
+            .with_inner_size(app::DEFAULT_WINDOW_SIZE)
+            .with_min_inner_size(app::MIN_WINDOW_SIZE)
             .with_window_level(egui::WindowLevel::Normal)
+            .with_transparent(true)
             .with_icon(load_icon().unwrap_or_else(|| Arc::new(IconData {
                 rgba: Vec::new(),
                 width: 0,
@@ -39,12 +59,25 @@ fn load_icon() -> Option<Arc<IconData>> {
     const ICON: &[u8] = include_bytes!("assets/veritas.ico");
     image::load_from_memory(ICON)
         .ok()
-        .and_then(|img| {
+        .map(|img| {
             let rgba = img.into_rgba8();
-            Some(Arc::new(IconData {
+            Arc::new(IconData {
                 rgba: rgba.to_vec(),
                 width: rgba.width(),
                 height: rgba.height(),
-            }))
+            })
         })
-}
\ No newline at end of file
+}
+
+// Release builds use the windows subsystem and have no console of their own,
+// so borrow the terminal a subcommand was started from
+#[cfg(target_os = "windows")]
+fn attach_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_console() {}
@@ -1,5 +1,5 @@
-mod av_panel;
-mod central_panel;
+pub mod compact_panel;
+mod dock_panel;
 mod menubar_panel;
-mod sidebar_panel;
-mod statusbar_panel;
\ No newline at end of file
+mod statusbar_panel;
+pub mod settings_window;
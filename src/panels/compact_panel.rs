@@ -0,0 +1,125 @@
+use eframe::egui::{
+    self, Align, Align2, Id, Key, KeyboardShortcut, Layout, Modifiers, RichText, Sense, TextStyle, Vec2, ViewportCommand,
+    WindowLevel,
+};
+use egui_material_icons::icons::ICON_OPEN_IN_FULL;
+use veritas_core::i18n::tr;
+
+use crate::app::{DamageAnalyzer, DEFAULT_WINDOW_SIZE, MIN_WINDOW_SIZE};
+use crate::core::helpers;
+
+pub const COMPACT_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::CTRL.plus(Modifiers::SHIFT), Key::M);
+
+const COMPACT_WIDTH: f32 = 320.0;
+const BAR_HEIGHT: f32 = 18.0;
+
+impl DamageAnalyzer {
+    /// Keeps the window above others while pinned or compact
+    pub fn apply_window_level(&self, ctx: &egui::Context) {
+        let level = if self.state.is_window_pinned || self.state.is_compact {
+            WindowLevel::AlwaysOnTop
+        } else {
+            WindowLevel::Normal
+        };
+        ctx.send_viewport_cmd(ViewportCommand::WindowLevel(level));
+    }
+
+    pub fn toggle_compact(&mut self, ctx: &egui::Context) {
+        self.state.is_compact = !self.state.is_compact;
+        if self.state.is_compact {
+            self.state.expanded_size = ctx.input(|i| i.viewport().inner_rect).map(|rect| rect.size());
+            self.state.compact_size = None;
+            ctx.send_viewport_cmd(ViewportCommand::Decorations(false));
+            ctx.send_viewport_cmd(ViewportCommand::MinInnerSize(Vec2::new(COMPACT_WIDTH, BAR_HEIGHT)));
+        } else {
+            ctx.send_viewport_cmd(ViewportCommand::Decorations(true));
+            ctx.send_viewport_cmd(ViewportCommand::MinInnerSize(MIN_WINDOW_SIZE.into()));
+            let size = self.state.expanded_size.take().unwrap_or(DEFAULT_WINDOW_SIZE.into());
+            ctx.send_viewport_cmd(ViewportCommand::InnerSize(size));
+        }
+        self.apply_window_level(ctx);
+    }
+
+    /// Borderless strip with the headline numbers and a bar per character
+    pub fn show_compact_panel(&mut self, ctx: &egui::Context) {
+        let data_buffer = self.data_buffer.blocking_lock().clone();
+        let (preferences, colors) = self.state.config.read(|config| {
+            (config.preferences.clone(), config.character_colors.clone())
+        });
+        let number_format = preferences.number_format;
+
+        let frame = egui::Frame::central_panel(&ctx.style())
+            .fill(ctx.style().visuals.panel_fill.gamma_multiply(preferences.compact_opacity));
+        let margin = frame.total_margin().sum().y;
+        let used_height = egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
+            ui.style_mut().interaction.selectable_labels = false;
+
+            // Registered first so the widgets on top keep their clicks
+            let background = ui.interact(ui.max_rect(), Id::new("compact_background"), Sense::click_and_drag());
+            if background.drag_started() {
+                ctx.send_viewport_cmd(ViewportCommand::StartDrag);
+            }
+            let mut leave = background.double_clicked();
+
+            ui.horizontal(|ui| {
+                for (label, value) in [
+                    (tr("AV"), format!("{:.2}", data_buffer.current_av)),
+                    (tr("Total"), number_format.format(data_buffer.total_damage.values().sum::<f64>())),
+                    (tr("DpAV"), number_format.format(data_buffer.total_dpav)),
+                ] {
+                    ui.weak(label);
+                    ui.label(RichText::new(value).strong());
+                    ui.add_space(4.0);
+                }
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    leave |= ui
+                        .small_button(ICON_OPEN_IN_FULL)
+                        .on_hover_text(format!("{} ({})", tr("Leave Compact Mode"), ctx.format_shortcut(&COMPACT_SHORTCUT)))
+                        .clicked();
+                });
+            });
+
+            let mut totals: Vec<(&String, f64)> = data_buffer
+                .column_names
+                .iter()
+                .filter_map(|name| data_buffer.total_damage.get(name).map(|&damage| (name, damage)))
+                .collect();
+            totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+            let total: f64 = totals.iter().map(|(_, damage)| damage).sum();
+            let max = totals.first().map_or(0.0, |(_, damage)| *damage);
+
+            let font = TextStyle::Small.resolve(ui.style());
+            let text_color = ui.visuals().strong_text_color();
+            for (name, damage) in totals {
+                let (rect, _) = ui.allocate_exact_size(Vec2::new(ui.available_width(), BAR_HEIGHT), Sense::hover());
+                let fraction = if max > 0.0 { (damage / max) as f32 } else { 0.0 };
+                let color = helpers::get_character_color(&colors, preferences.palette, name);
+                let painter = ui.painter();
+                painter.rect_filled(rect.with_max_x(rect.left() + rect.width() * fraction), 2.0, color.gamma_multiply(0.8));
+                painter.text(rect.left_center() + Vec2::new(4.0, 0.0), Align2::LEFT_CENTER, name, font.clone(), text_color);
+                let share = if total > 0.0 { damage / total * 100.0 } else { 0.0 };
+                painter.text(
+                    rect.right_center() - Vec2::new(4.0, 0.0),
+                    Align2::RIGHT_CENTER,
+                    format!("{} ({:.1}%)", number_format.format(damage), share),
+                    font.clone(),
+                    text_color,
+                );
+            }
+
+            if leave {
+                self.toggle_compact(ctx);
+            }
+            ui.cursor().top() - ui.max_rect().top() - ui.spacing().item_spacing.y
+        }).inner;
+
+        // Grow and shrink with the lineup instead of leaving a gap below the bars
+        if self.state.is_compact {
+            let size = Vec2::new(COMPACT_WIDTH, (used_height + margin).ceil());
+            if self.state.compact_size != Some(size) {
+                ctx.send_viewport_cmd(ViewportCommand::InnerSize(size));
+                self.state.compact_size = Some(size);
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use eframe::egui::{
+    self, Align, CursorIcon, DragAndDrop, Id, LayerId, Layout, Order, Pos2, Rect, Sense, Ui, UiBuilder, ViewportBuilder,
+    ViewportClass, ViewportId,
+};
+use egui_material_icons::icons::{ICON_CLOSE, ICON_OPEN_IN_NEW};
+use veritas_core::i18n::tr;
+
+use crate::app::{DamageAnalyzer, GraphMode};
+use crate::core::layout::{DetachedWindow, DockNode, DropZone, Pane, SplitDirection};
+
+const TAB_BAR_HEIGHT: f32 = 24.0;
+const SEPARATOR_WIDTH: f32 = 6.0;
+/// How close to a group's edge, as a share of its size, a drop splits it
+const EDGE_ZONE: f32 = 0.25;
+
+/// Drag payload of a tab
+struct DraggedPane(Pane);
+
+enum DockAction {
+    Activate(Pane),
+    Close(Pane),
+    Detach(Pane),
+    Dock { pane: Pane, target: Pane, zone: DropZone },
+    Resized,
+}
+
+impl DamageAnalyzer {
+    pub fn show_dock_panel(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            // Taken out so panes can borrow the app while the tree is walked
+            let mut root = self.state.layout.root.take();
+            let mut actions = Vec::new();
+            match &mut root {
+                Some(root) => {
+                    let rect = ui.available_rect_before_wrap();
+                    self.show_dock_node(ui, root, rect, Id::new("dock"), &mut actions);
+                }
+                None => {
+                    ui.centered_and_justified(|ui| {
+                        ui.weak(tr("Every pane is closed, reopen them from the View menu"));
+                    });
+                }
+            }
+            self.state.layout.root = root;
+
+            if actions.is_empty() {
+                return;
+            }
+            for action in actions {
+                match action {
+                    DockAction::Activate(pane) => self.state.layout.activate(pane),
+                    DockAction::Close(pane) => self.state.layout.close(pane),
+                    DockAction::Detach(pane) => {
+                        let previous = self.state.window_geometry.get(&pane).cloned();
+                        self.state.layout.detach(pane, previous);
+                    }
+                    DockAction::Dock { pane, target, zone } => self.state.layout.dock(pane, target, zone),
+                    DockAction::Resized => {}
+                }
+            }
+            self.remember_session();
+        });
+    }
+
+    /// Shows each detached pane in its own native window, docking it back once closed
+    pub fn show_detached_windows(&mut self, ctx: &egui::Context) {
+        for window in self.state.layout.detached.clone() {
+            let pane = window.pane;
+            let mut builder = ViewportBuilder::default()
+                .with_title(format!("{} - Veritas", tr(pane.label())))
+                .with_inner_size(window.size);
+            if let Some(position) = window.position {
+                builder = builder.with_position(position);
+            }
+
+            let closed = ctx.show_viewport_immediate(ViewportId::from_hash_of(("detached", pane)), builder, |ctx, class| {
+                // Backends without multiple viewports get an egui window instead
+                if class == ViewportClass::Embedded {
+                    let mut open = true;
+                    egui::Window::new(tr(pane.label()))
+                        .id(Id::new(("detached", pane)))
+                        .default_size(window.size)
+                        .open(&mut open)
+                        .show(ctx, |ui| self.show_pane(ui, pane));
+                    return !open;
+                }
+
+                egui::CentralPanel::default().show(ctx, |ui| self.show_pane(ui, pane));
+                let (outer, inner, close_requested) = ctx.input(|i| {
+                    (i.viewport().outer_rect, i.viewport().inner_rect, i.viewport().close_requested())
+                });
+                if let (Some(outer), Some(inner)) = (outer, inner) {
+                    self.state.window_geometry.insert(pane, DetachedWindow {
+                        pane,
+                        position: Some(outer.min.into()),
+                        size: inner.size().into(),
+                    });
+                }
+                close_requested
+            });
+
+            if closed {
+                self.state.layout.attach(pane);
+                self.remember_session();
+            }
+        }
+    }
+
+    fn show_dock_node(&mut self, ui: &mut Ui, node: &mut DockNode, rect: Rect, id: Id, actions: &mut Vec<DockAction>) {
+        match node {
+            DockNode::Split { direction, fraction, first, second } => {
+                let horizontal = *direction == SplitDirection::Horizontal;
+                let (start, extent) = if horizontal {
+                    (rect.left(), rect.width())
+                } else {
+                    (rect.top(), rect.height())
+                };
+                let at = start + extent * *fraction;
+                let half = SEPARATOR_WIDTH / 2.0;
+                let (first_rect, separator, second_rect) = if horizontal {
+                    (
+                        rect.with_max_x(at - half),
+                        Rect::from_x_y_ranges(at - half..=at + half, rect.y_range()),
+                        rect.with_min_x(at + half),
+                    )
+                } else {
+                    (
+                        rect.with_max_y(at - half),
+                        Rect::from_x_y_ranges(rect.x_range(), at - half..=at + half),
+                        rect.with_min_y(at + half),
+                    )
+                };
+
+                let cursor = if horizontal { CursorIcon::ResizeHorizontal } else { CursorIcon::ResizeVertical };
+                let response = ui.interact(separator, id.with("separator"), Sense::drag()).on_hover_cursor(cursor);
+                if response.dragged() {
+                    if let Some(pointer) = response.interact_pointer_pos() {
+                        let position = if horizontal { pointer.x } else { pointer.y };
+                        *fraction = ((position - start) / extent).clamp(0.1, 0.9);
+                    }
+                }
+                if response.drag_stopped() {
+                    actions.push(DockAction::Resized);
+                }
+                let stroke = if response.hovered() || response.dragged() {
+                    ui.visuals().widgets.active.bg_stroke
+                } else {
+                    ui.visuals().widgets.noninteractive.bg_stroke
+                };
+                if horizontal {
+                    ui.painter().vline(at, rect.y_range(), stroke);
+                } else {
+                    ui.painter().hline(rect.x_range(), at, stroke);
+                }
+
+                self.show_dock_node(ui, first, first_rect, id.with(0), actions);
+                self.show_dock_node(ui, second, second_rect, id.with(1), actions);
+            }
+            DockNode::Tabs { panes, active } => {
+                // Loaded layouts are normalized, so an empty group has nothing to show anyway
+                if let Some(&active) = panes.get(*active).or(panes.last()) {
+                    self.show_tab_group(ui, panes, active, rect, id, actions);
+                }
+            }
+        }
+    }
+
+    fn show_tab_group(&mut self, ui: &mut Ui, panes: &[Pane], active: Pane, rect: Rect, id: Id, actions: &mut Vec<DockAction>) {
+        let bar_rect = rect.with_max_y(rect.top() + TAB_BAR_HEIGHT);
+        let content_rect = rect.with_min_y(bar_rect.bottom()).shrink(4.0);
+
+        ui.painter().rect_filled(bar_rect, 0.0, ui.visuals().faint_bg_color);
+        ui.scope_builder(
+            UiBuilder::new().max_rect(bar_rect).layout(Layout::left_to_right(Align::Center)),
+            |ui| {
+                ui.set_clip_rect(bar_rect.intersect(ui.clip_rect()));
+                for &pane in panes {
+                    ui.dnd_drag_source(Id::new(("dock_tab", pane)), DraggedPane(pane), |ui| {
+                        if ui.selectable_label(pane == active, tr(pane.label())).clicked() {
+                            actions.push(DockAction::Activate(pane));
+                        }
+                        if ui.small_button(ICON_OPEN_IN_NEW).on_hover_text(tr("Open in New Window")).clicked() {
+                            actions.push(DockAction::Detach(pane));
+                        }
+                        if ui.small_button(ICON_CLOSE).on_hover_text(tr("Close")).clicked() {
+                            actions.push(DockAction::Close(pane));
+                        }
+                    });
+                }
+            },
+        );
+
+        ui.scope_builder(UiBuilder::new().max_rect(content_rect).id_salt(active), |ui| {
+            ui.set_clip_rect(content_rect.intersect(ui.clip_rect()));
+            self.show_pane(ui, active);
+        });
+
+        let Some(dragged) = DragAndDrop::payload::<DraggedPane>(ui.ctx()) else {
+            return;
+        };
+        let Some(pointer) = ui.ctx().pointer_hover_pos().filter(|pointer| rect.contains(*pointer)) else {
+            return;
+        };
+        let zone = drop_zone(bar_rect, content_rect, pointer);
+        let preview = match zone {
+            DropZone::Center => rect,
+            DropZone::Left => rect.with_max_x(rect.center().x),
+            DropZone::Right => rect.with_min_x(rect.center().x),
+            DropZone::Top => rect.with_max_y(rect.center().y),
+            DropZone::Bottom => rect.with_min_y(rect.center().y),
+        };
+        ui.ctx()
+            .layer_painter(LayerId::new(Order::Foreground, id.with("drop")))
+            .rect_filled(preview, 2.0, ui.visuals().selection.bg_fill.gamma_multiply(0.4));
+        // The payload is cleared at the end of the frame the pointer is released in
+        if ui.input(|i| i.pointer.any_released()) {
+            actions.push(DockAction::Dock { pane: dragged.0, target: active, zone });
+        }
+    }
+
+    fn show_pane(&mut self, ui: &mut Ui, pane: Pane) {
+        // These keep their own scroll areas, the log to stick to the bottom
+        // and the tables to keep their headers in view
+        match pane {
+            Pane::Log => {
+                self.show_log_widget(ui);
+                return;
+            }
+            Pane::Stats => {
+                self.show_character_stats_widget(ui);
+                return;
+            }
+            Pane::Turns => {
+                self.show_turn_table_widget(ui);
+                return;
+            }
+            _ => {}
+        }
+        egui::ScrollArea::vertical().id_salt(pane).auto_shrink(false).show(ui, |ui| match pane {
+            Pane::TurnDamage => {
+                self.show_graph_mode_toggle(ui);
+                self.show_turn_damage_plot_widget(ui);
+            }
+            Pane::AvDamage => {
+                self.show_graph_mode_toggle(ui);
+                self.show_av_damage_plot_widget(ui);
+            }
+            Pane::Distribution => self.show_damage_distribution_widget(ui),
+            Pane::Histogram => self.show_hit_histogram_widget(ui),
+            Pane::Heatmap => self.show_damage_heatmap_widget(ui),
+            Pane::Totals => self.show_damage_bar_widget(ui),
+            Pane::Metrics => self.show_av_metrics_widget(ui),
+            Pane::Dpav => self.show_dpav_plot_widget(ui),
+            Pane::Timeline => self.show_av_timeline_widget(ui),
+            Pane::Log | Pane::Stats | Pane::Turns => {}
+        });
+    }
+
+    /// Shared by both damage plots, so side by side they always agree
+    fn show_graph_mode_toggle(&mut self, ui: &mut Ui) {
+        let changed = ui.horizontal(|ui| {
+            let mut changed = false;
+            for mode in GraphMode::ALL {
+                changed |= ui.radio_value(&mut self.state.graph_mode, mode, tr(mode.label())).changed();
+            }
+            changed
+        }).inner;
+        if changed {
+            self.remember_session();
+        }
+    }
+}
+
+fn drop_zone(bar_rect: Rect, content_rect: Rect, pointer: Pos2) -> DropZone {
+    if bar_rect.contains(pointer) {
+        return DropZone::Center;
+    }
+    let x = (pointer.x - content_rect.left()) / content_rect.width();
+    let y = (pointer.y - content_rect.top()) / content_rect.height();
+    [(x, DropZone::Left), (1.0 - x, DropZone::Right), (y, DropZone::Top), (1.0 - y, DropZone::Bottom)]
+        .into_iter()
+        .filter(|(distance, _)| *distance < EDGE_ZONE)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map_or(DropZone::Center, |(_, zone)| zone)
+}
@@ -1,63 +1,152 @@
-use eframe::egui::{self, RichText, ThemePreference, Hyperlink};
+use eframe::egui::{self, RichText, Hyperlink};
 use rfd::FileDialog;
 use std::fs;
 use egui_toast::{Toast, ToastKind};
 use egui_toast::ToastOptions;
 
-use crate::{app::DamageAnalyzer, core::launcher::{hijack_process, start_hijacked_process}};
+use crate::app::DamageAnalyzer;
+use crate::core::layout::{DockLayout, Pane};
+use crate::core::platform::{game_launcher, LAUNCHER_UNAVAILABLE};
+use crate::core::file_logger;
 use crate::core::updater::VeritasVersion;
+use crate::core::live_stats::DEFAULT_LIVE_STATS_PORT;
+use veritas_core::i18n::tr;
+use veritas_core::tr;
 
 impl DamageAnalyzer {
     pub fn show_menubar_panel(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
-                ui.menu_button("File", |ui| {
-                    if ui.button("Launch Game...").clicked() {
+                ui.menu_button(tr("File"), |ui| {
+                    if ui
+                        .add_enabled(game_launcher().is_some(), egui::Button::new(tr("Launch Game...")))
+                        .on_disabled_hover_text(tr(LAUNCHER_UNAVAILABLE))
+                        .clicked()
+                    {
                         self.state.show_launcher = true;
                         ui.close_menu();
                     }
-                    if ui.button("Connection Settings...").clicked() {
+                    if ui.button(tr("Connection Settings...")).clicked() {
                         self.state.show_connection_settings = true;
                         ui.close_menu();
                     }
-                    if ui.button("Preferences...").clicked() {
+                    if ui.button(tr("Preferences...")).clicked() {
                         self.state.show_preferences = true;
                         ui.close_menu();
                     }
                     
                     ui.separator();
                     
-                    if ui.button("Updates...").clicked() {
+                    if ui.button(tr("Updates...")).clicked() {
                         self.state.show_updates = true;
                         ui.close_menu();
                     }
+                    if ui.button(tr("Open Log Folder")).clicked() {
+                        let dir = file_logger::log_dir();
+                        if let Err(e) = fs::create_dir_all(&dir).and_then(|_| open::that(&dir)) {
+                            log::error!("Failed to open log folder {:?}: {}", dir, e);
+                        }
+                        ui.close_menu();
+                    }
 
                     ui.separator();
                     
-                    if ui.button("About...").clicked() {
+                    if ui.button(tr("About...")).clicked() {
                         self.state.show_about = true;
                         ui.close_menu();
                     }
                 });
+
+                ui.menu_button(tr("View"), |ui| {
+                    for pane in Pane::ALL {
+                        let mut open = self.state.layout.contains(pane);
+                        if ui.checkbox(&mut open, tr(pane.label())).changed() {
+                            if open {
+                                self.state.layout.open(pane);
+                            } else {
+                                self.state.layout.close(pane);
+                            }
+                            self.remember_session();
+                        }
+                    }
+
+                    ui.separator();
+
+                    let layouts = self.state.config.read(|config| config.layouts.clone());
+                    ui.menu_button(tr("Layouts"), |ui| {
+                        if ui.button(tr("Default")).clicked() {
+                            self.load_layout(DockLayout::default());
+                            ui.close_menu();
+                        }
+                        if !layouts.is_empty() {
+                            ui.separator();
+                        }
+                        for (name, layout) in &layouts {
+                            if ui.button(name).clicked() {
+                                self.load_layout(layout.clone());
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    if ui.button(tr("Save Layout As...")).clicked() {
+                        self.state.layout_name = Some(String::new());
+                        ui.close_menu();
+                    }
+                    ui.add_enabled_ui(!layouts.is_empty(), |ui| {
+                        ui.menu_button(tr("Delete Layout"), |ui| {
+                            for name in layouts.keys() {
+                                if ui.button(name).clicked() {
+                                    self.state.config.update(|config| {
+                                        config.layouts.remove(name);
+                                    });
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    });
+                });
                 
-                ui.menu_button("Tools", |ui| {
-                    if ui.button("Spawn Server").clicked() {
-                        hijack_process("StarRail", "veritas.dll");
+                ui.menu_button(tr("Tools"), |ui| {
+                    if ui
+                        .add_enabled(game_launcher().is_some(), egui::Button::new(tr("Spawn Server")))
+                        .on_disabled_hover_text(tr(LAUNCHER_UNAVAILABLE))
+                        .clicked()
+                    {
+                        if let Some(launcher) = game_launcher() {
+                            launcher.inject_running("StarRail", "veritas.dll");
+                        }
+                    }
+
+                    ui.separator();
+
+                    let mut live_stats_enabled = self.live_stats.running_port().is_some();
+                    if ui.checkbox(&mut live_stats_enabled, tr("Live Stats Server")).changed() {
+                        self.state.config.update(|config| config.live_stats_enabled = live_stats_enabled);
+                    }
+                    if let Some(port) = self.live_stats.running_port() {
+                        ui.hyperlink_to(
+                            format!("http://127.0.0.1:{}/api/battle", port),
+                            format!("http://127.0.0.1:{}/api/battle", port),
+                        );
+                        ui.hyperlink_to(
+                            tr("Open OBS Overlay"),
+                            format!("http://127.0.0.1:{}/overlay", port),
+                        );
                     }
                 });
             });
         });
 
         if self.state.show_updates {
-            egui::Window::new("Updates")
+            egui::Window::new(tr("Updates"))
                 .collapsible(false)
                 .resizable(false)
                 .show(ctx, |ui| {
                     ui.vertical(|ui| {
                         ui.group(|ui| {
-                            ui.heading("Veritas App");
+                            ui.heading(tr("Veritas App"));
                             ui.vertical(|ui| {
-                                ui.label(format!("Current Version: {}", env!("CARGO_PKG_VERSION")));
+                                ui.label(tr!("Current Version: {}", env!("CARGO_PKG_VERSION")));
                                 
                                 let latest_app_version = self.updater.latest_app_version();
                                 let latest_version = self.state.checked_app_version.as_ref()
@@ -65,13 +154,13 @@ impl DamageAnalyzer {
                                 
                                 if let Some(latest_app) = latest_version {
                                     if latest_app != env!("CARGO_PKG_VERSION") {
-                                        ui.label(RichText::new(format!("Available Version: {}", latest_app))
+                                        ui.label(RichText::new(tr!("Available Version: {}", latest_app))
                                             .color(egui::Color32::GREEN));
                                     }
                                 }
                             });
                             ui.horizontal(|ui| {
-                                if ui.button("Check for Updates").clicked() {
+                                if ui.button(tr("Check for Updates")).clicked() {
                                     let toasts = self.toasts.clone();
                                     let mut updater = self.updater.clone();
                                     let (tx, rx) = tokio::sync::oneshot::channel();
@@ -79,7 +168,7 @@ impl DamageAnalyzer {
                                     self.runtime.spawn(async move {
                                         if let Ok(mut toast_lock) = toasts.try_lock() {
                                             toast_lock.add(Toast {
-                                                text: "Checking for updates...".into(),
+                                                text: tr("Checking for updates...").into(),
                                                 kind: ToastKind::Info,
                                                 options: ToastOptions::default()
                                                     .duration_in_seconds(2.0),
@@ -93,7 +182,7 @@ impl DamageAnalyzer {
                                             if new_version != env!("CARGO_PKG_VERSION") {
                                                 if let Ok(mut toast_lock) = toasts.try_lock() {
                                                     toast_lock.add(Toast {
-                                                        text: format!("Veritas App v{} is available!", new_version).into(),
+                                                        text: tr!("Veritas App v{} is available!", new_version).into(),
                                                         kind: ToastKind::Success,
                                                         options: ToastOptions::default()
                                                             .duration_in_seconds(5.0),
@@ -103,7 +192,7 @@ impl DamageAnalyzer {
                                             } else {
                                                 if let Ok(mut toast_lock) = toasts.try_lock() {
                                                     toast_lock.add(Toast {
-                                                        text: "Veritas App is up to date".into(),
+                                                        text: tr("Veritas App is up to date").into(),
                                                         kind: ToastKind::Info,
                                                         options: ToastOptions::default()
                                                             .duration_in_seconds(3.0),
@@ -127,16 +216,16 @@ impl DamageAnalyzer {
                                 let update_state = self.state.update_state.clone();
                                 
                                 ui.add_enabled_ui(update_available, |ui| {
-                                    if ui.button("Download Update").clicked() {
+                                    if ui.button(tr("Download Update")).clicked() {
                                         let toasts = self.toasts.clone();
                                         let mut updater = self.updater.clone();
                                         let update_state = update_state.clone();
                                         
                                         self.runtime.spawn(async move {
-                                            if let Some(_) = updater.download_update().await {
+                                            if updater.download_update().await.is_some() {
                                                 if let Ok(mut toast_lock) = toasts.try_lock() {
                                                     toast_lock.add(Toast {
-                                                        text: "Update downloaded. Click 'Restart to Apply' to apply.".into(),
+                                                        text: tr("Update downloaded. Click 'Restart to Apply' to apply.").into(),
                                                         kind: ToastKind::Success,
                                                         options: ToastOptions::default()
                                                             .duration_in_seconds(30.0),
@@ -156,9 +245,9 @@ impl DamageAnalyzer {
                                     .unwrap_or(false);
 
                                 ui.add_enabled_ui(update_downloaded, |ui| {
-                                    if ui.button("Restart to Apply").clicked() {
+                                    if ui.button(tr("Restart to Apply")).clicked() {
                                         if let Ok(current_exe) = std::env::current_exe() {
-                                            println!("Current exe: {:?}", current_exe);
+                                            log::info!("Current exe: {:?}", current_exe);
                                             let parent = current_exe.parent().unwrap();
                                             let file_name = current_exe.file_name().unwrap().to_str().unwrap();
 
@@ -171,13 +260,13 @@ impl DamageAnalyzer {
                                                 current_exe.with_extension("old"),
                                             ];
 
-                                            println!("Checking possible update files:");
+                                            log::debug!("Checking possible update files:");
                                             for path in &possible_paths {
-                                                println!("  {:?} (exists={})", path, path.exists());
+                                                log::debug!("  {:?} (exists={})", path, path.exists());
                                             }
 
                                             if let Some(update_path) = possible_paths.iter().find(|p| p.exists()) {
-                                                println!("Found update at: {:?}", update_path);
+                                                log::info!("Found update at: {:?}", update_path);
                                                 
                                                 std::thread::sleep(std::time::Duration::from_millis(500));
 
@@ -185,10 +274,10 @@ impl DamageAnalyzer {
                                                 let backup_path = current_exe.with_extension("old");
 
                                                 if let Err(e) = fs::rename(&current_exe, &backup_path) {
-                                                    println!("Failed to backup current exe: {}", e);
+                                                    log::error!("Failed to backup current exe: {}", e);
                                                     if let Ok(mut toast_lock) = self.toasts.try_lock() {
                                                         toast_lock.add(Toast {
-                                                            text: format!("Update failed: {}", e).into(),
+                                                            text: tr!("Update failed: {}", e).into(),
                                                             kind: ToastKind::Error,
                                                             options: ToastOptions::default()
                                                                 .duration_in_seconds(5.0),
@@ -199,11 +288,11 @@ impl DamageAnalyzer {
                                                 }
 
                                                 if let Err(e) = fs::rename(update_path, &current_exe) {
-                                                    println!("Failed to move new exe: {}", e);
+                                                    log::error!("Failed to move new exe: {}", e);
                                                     let _ = fs::rename(&backup_path, &current_exe);
                                                     if let Ok(mut toast_lock) = self.toasts.try_lock() {
                                                         toast_lock.add(Toast {
-                                                            text: format!("Update failed: {}", e).into(),
+                                                            text: tr!("Update failed: {}", e).into(),
                                                             kind: ToastKind::Error,
                                                             options: ToastOptions::default()
                                                                 .duration_in_seconds(5.0),
@@ -218,20 +307,20 @@ impl DamageAnalyzer {
                                                     .spawn() 
                                                 {
                                                     Ok(_) => {
-                                                        println!("Successfully launched new version");
+                                                        log::info!("Successfully launched new version");
                                                         if let Err(e) = fs::remove_file(&backup_path) {
-                                                            println!("Failed to remove old version: {}", e);
+                                                            log::warn!("Failed to remove old version: {}", e);
                                                         } else {
-                                                            println!("Cleaned up old version");
+                                                            log::info!("Cleaned up old version");
                                                         }
                                                         std::process::exit(0);
                                                     },
                                                     Err(e) => {
-                                                        println!("Failed to launch new version: {}", e);
+                                                        log::error!("Failed to launch new version: {}", e);
                                                         let _ = fs::rename(&backup_path, &current_exe);
                                                         if let Ok(mut toast_lock) = self.toasts.try_lock() {
                                                             toast_lock.add(Toast {
-                                                                text: format!("Failed to restart: {}", e).into(),
+                                                                text: tr!("Failed to restart: {}", e).into(),
                                                                 kind: ToastKind::Error,
                                                                 options: ToastOptions::default()
                                                                     .duration_in_seconds(5.0),
@@ -241,10 +330,10 @@ impl DamageAnalyzer {
                                                     }
                                                 }
                                             } else {
-                                                println!("No update file found");
+                                                log::error!("No update file found");
                                                 if let Ok(mut toast_lock) = self.toasts.try_lock() {
                                                     toast_lock.add(Toast {
-                                                        text: "Could not find update file".into(),
+                                                        text: tr("Could not find update file").into(),
                                                         kind: ToastKind::Error,
                                                         options: ToastOptions::default()
                                                             .duration_in_seconds(5.0),
@@ -261,63 +350,62 @@ impl DamageAnalyzer {
                         ui.add_space(8.0);
 
                         ui.group(|ui| {
-                            ui.heading("Veritas");
+                            ui.heading(tr("Veritas"));
                             ui.horizontal(|ui| {
-                                ui.label("Version Type:");
+                                ui.label(tr("Version Type:"));
+                                let mut version_type = self.state.config.read(|config| config.version_type.clone());
                                 egui::ComboBox::new("version_type", "")
-                                    .selected_text(match self.updater.current_version_type {
-                                        VeritasVersion::GlobalBeta => "Global Beta",
-                                        VeritasVersion::CnBeta => "CN Beta",
-                                        VeritasVersion::GlobalProd => "Global Prod",
+                                    .selected_text(match version_type {
+                                        VeritasVersion::GlobalBeta => tr("Global Beta"),
+                                        VeritasVersion::CnBeta => tr("CN Beta"),
+                                        VeritasVersion::GlobalProd => tr("Global Prod"),
                                     })
                                     .show_ui(ui, |ui| {
                                         let mut changed = false;
-                                        ui.selectable_value(&mut self.updater.current_version_type, 
-                                            VeritasVersion::GlobalBeta, "Global Beta").clicked()
+                                        ui.selectable_value(&mut version_type, 
+                                            VeritasVersion::GlobalBeta, tr("Global Beta")).clicked()
                                             .then(|| changed = true);
-                                        ui.selectable_value(&mut self.updater.current_version_type, 
-                                            VeritasVersion::CnBeta, "CN Beta").clicked()
+                                        ui.selectable_value(&mut version_type, 
+                                            VeritasVersion::CnBeta, tr("CN Beta")).clicked()
                                             .then(|| changed = true);
-                                        ui.selectable_value(&mut self.updater.current_version_type, 
-                                            VeritasVersion::GlobalProd, "Global Prod").clicked()
+                                        ui.selectable_value(&mut version_type, 
+                                            VeritasVersion::GlobalProd, tr("Global Prod")).clicked()
                                             .then(|| changed = true);
                                         
                                         if changed {
-                                            self.state.config.version_type = Some(self.updater.current_version_type.clone());
-                                            self.state.config.save();
+                                            self.state.config.update(|config| config.version_type = version_type);
                                         }
                                     });
                             });
                             ui.horizontal(|ui| {
                                 ui.vertical(|ui| {
                                     if let Some(current_version) = self.updater.current_dll_version() {
-                                        ui.label(format!("Current Version: {}", current_version));
+                                        ui.label(tr!("Current Version: {}", current_version));
                                         
                                         let latest_version = self.state.checked_dll_version.clone()
                                             .or_else(|| self.updater.latest_dll_version());
                                             
                                         if let Some(latest_dll) = latest_version {
                                             if latest_dll != current_version {
-                                                ui.label(RichText::new(format!("Available Version: {}", latest_dll))
+                                                ui.label(RichText::new(tr!("Available Version: {}", latest_dll))
                                                     .color(egui::Color32::GREEN));
                                             }
                                         }
                                     } else {
-                                        ui.label("Not installed");
+                                        ui.label(tr("Not installed"));
                                     }
                                 });
                             });
                             ui.horizontal(|ui| {
-                                if ui.button("Check for Updates").clicked() {
+                                if ui.button(tr("Check for Updates")).clicked() {
                                     let toasts = self.toasts.clone();
                                     let mut updater = self.updater.clone();
-                                    let config = self.state.config.clone();
                                     let (tx, rx) = tokio::sync::oneshot::channel();
                                     
                                     self.runtime.spawn(async move {
                                         if let Ok(mut toast_lock) = toasts.try_lock() {
                                             toast_lock.add(Toast {
-                                                text: "Checking for updates...".into(),
+                                                text: tr("Checking for updates...").into(),
                                                 kind: ToastKind::Info,
                                                 options: ToastOptions::default()
                                                     .duration_in_seconds(2.0),
@@ -326,11 +414,12 @@ impl DamageAnalyzer {
                                         }
 
                                         if let Some(new_version) = updater.check_dll_update().await {
+                                            let is_new = updater.current_dll_version().as_ref() != Some(&new_version);
                                             let _ = tx.send((new_version.clone(), updater));
-                                            if new_version != config.dll_version.unwrap_or_default() {
+                                            if is_new {
                                                 if let Ok(mut toast_lock) = toasts.try_lock() {
                                                     toast_lock.add(Toast {
-                                                        text: format!("Veritas version {} is available!", new_version).into(),
+                                                        text: tr!("Veritas version {} is available!", new_version).into(),
                                                         kind: ToastKind::Success,
                                                         options: ToastOptions::default()
                                                             .duration_in_seconds(5.0),
@@ -340,7 +429,7 @@ impl DamageAnalyzer {
                                             } else {
                                                 if let Ok(mut toast_lock) = toasts.try_lock() {
                                                     toast_lock.add(Toast {
-                                                        text: "Veritas is up to date".into(),
+                                                        text: tr("Veritas is up to date").into(),
                                                         kind: ToastKind::Info,
                                                         options: ToastOptions::default()
                                                             .duration_in_seconds(3.0),
@@ -367,25 +456,20 @@ impl DamageAnalyzer {
                                 };
 
                                 ui.add_enabled_ui(update_available, |ui| {
-                                    if ui.button("Download Update").clicked() {
+                                    if ui.button(tr("Download Update")).clicked() {
                                         let toasts = self.toasts.clone();
                                         let mut updater = self.updater.clone();
-                                        let config = self.state.config.clone();
                                         let (tx, rx) = tokio::sync::oneshot::channel();
                                         
                                         self.runtime.spawn(async move {
                                             match updater.update_dll().await {
                                                 Ok(()) => {
                                                     if let Some(new_version) = updater.latest_dll_version() {
-                                                        let mut config = config.clone();
-                                                        config.dll_version = Some(new_version.clone());
-                                                        config.save();
-
-                                                        let _ = tx.send(config);
+                                                        let _ = tx.send(());
 
                                                         if let Ok(mut toast_lock) = toasts.try_lock() {
                                                             toast_lock.add(Toast {
-                                                                text: format!("Veritas version {} has been downloaded!", new_version).into(),
+                                                                text: tr!("Veritas version {} has been downloaded!", new_version).into(),
                                                                 kind: ToastKind::Success,
                                                                 options: ToastOptions::default()
                                                                     .duration_in_seconds(5.0),
@@ -397,7 +481,7 @@ impl DamageAnalyzer {
                                                 Err(e) => {
                                                     if let Ok(mut toast_lock) = toasts.try_lock() {
                                                         toast_lock.add(Toast {
-                                                            text: format!("Failed to download update: {}", e).into(),
+                                                            text: tr!("Failed to download update: {}", e).into(),
                                                             kind: ToastKind::Error,
                                                             options: ToastOptions::default()
                                                                 .duration_in_seconds(5.0),
@@ -408,8 +492,7 @@ impl DamageAnalyzer {
                                             }
                                         });
 
-                                        if let Ok(new_config) = rx.blocking_recv() {
-                                            self.state.config = new_config;
+                                        if let Ok(()) = rx.blocking_recv() {
                                             self.state.checked_dll_version = None;
                                             ctx.request_repaint();
                                         }
@@ -419,7 +502,7 @@ impl DamageAnalyzer {
                         });
 
                         ui.add_space(8.0);
-                        if ui.button("Close").clicked() {
+                        if ui.button(tr("Close")).clicked() {
                             self.state.show_updates = false;
                         }
                     });
@@ -427,25 +510,41 @@ impl DamageAnalyzer {
         }
 
         if self.state.show_connection_settings {
-            egui::Window::new("Connection Settings")
+            egui::Window::new(tr("Connection Settings"))
                 .collapsible(false)
                 .resizable(false)
                 .show(ctx, |ui| {
                     ui.horizontal(|ui| {
-                        ui.label("Server:");
+                        ui.label(tr("Server:"));
                         let binding = self.server_addr.clone();
                         let mut server_addr = binding.blocking_lock();
                         ui.text_edit_singleline(&mut (*server_addr));
                     });
                     ui.horizontal(|ui| {
-                        ui.label("Port:");
+                        ui.label(tr("Port:"));
                         let binding = self.server_port.clone();
                         let mut server_port = binding.blocking_lock();
                         ui.text_edit_singleline(&mut (*server_port));
                     });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Live Stats Port:"));
+                        let mut port = self
+                            .state
+                            .config
+                            .read(|config| config.live_stats_port)
+                            .unwrap_or(DEFAULT_LIVE_STATS_PORT);
+                        if ui.add(egui::DragValue::new(&mut port).range(1024..=65535)).changed() {
+                            self.state.config.preview(|config| config.live_stats_port = Some(port));
+                        }
+                        if self.live_stats.running_port().is_some_and(|running| running != port)
+                            && ui.button(tr("Restart")).clicked()
+                        {
+                            self.start_live_stats();
+                        }
+                    });
                     ui.separator();
                     ui.horizontal(|ui| {
-                        if ui.button("Cancel").clicked() {
+                        if ui.button(tr("Cancel")).clicked() {
                             self.state.show_connection_settings = false;
                         }
                     });
@@ -453,47 +552,68 @@ impl DamageAnalyzer {
         }
 
         if self.state.show_preferences {
-            egui::Window::new("Preferences")
+            self.show_settings_window(ctx);
+        }
+
+        if let Some(mut name) = self.state.layout_name.take() {
+            let mut open = true;
+            let mut done = false;
+            egui::Window::new(tr("Save Layout"))
                 .collapsible(false)
                 .resizable(false)
+                .open(&mut open)
                 .show(ctx, |ui| {
+                    let layouts = self.state.config.read(|config| config.layouts.clone());
                     ui.horizontal(|ui| {
-                        ui.label("Theme:");
-                        let mut pref = ThemePreference::from(ctx.theme());
-                        pref.radio_buttons(ui);
-                        ctx.set_theme(pref);
+                        ui.label(tr("Name:"));
+                        ui.text_edit_singleline(&mut name);
                     });
-
-                    ui.separator();
-                    if ui.button("Close").clicked() {
-                        self.state.show_preferences = false;
+                    let trimmed = name.trim().to_string();
+                    if layouts.contains_key(&trimmed) {
+                        ui.weak(tr("Replaces the saved layout with this name"));
                     }
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        let save = ui.add_enabled(!trimmed.is_empty(), egui::Button::new(tr("Save"))).clicked()
+                            || (!trimmed.is_empty() && ui.input(|i| i.key_pressed(egui::Key::Enter)));
+                        if save {
+                            let layout = self.current_layout();
+                            self.state.config.update(|config| {
+                                config.layouts.insert(trimmed.clone(), layout);
+                            });
+                            done = true;
+                        }
+                        if ui.button(tr("Cancel")).clicked() {
+                            done = true;
+                        }
+                    });
                 });
+            if open && !done {
+                self.state.layout_name = Some(name);
+            }
         }
         
         if self.state.show_launcher {
-            egui::Window::new("Launch Game")
+            egui::Window::new(tr("Launch Game"))
                 .collapsible(false)
                 .resizable(false)
                 .show(ctx, |ui| {
+                    let game_path = self.state.config.read(|config| config.game_path.clone());
                     ui.horizontal(|ui| {
-                        let path_text = self.state.game_path
-                            .as_ref()
-                            .map(|p| p.to_string())
-                            .unwrap_or_else(|| "No game selected".to_string());
+                        let path_text = game_path
+                            .clone()
+                            .unwrap_or_else(|| tr("No game selected").to_string());
                         
-                        ui.label("Game Path:");
+                        ui.label(tr("Game Path:"));
                         ui.label(&path_text);
                         
-                        if ui.button("Browse").clicked() {
+                        if ui.button(tr("Browse")).clicked() {
                             if let Some(path) = FileDialog::new()
-                                .add_filter("Executable", &["exe"])
+                                .add_filter(tr("Executable"), &["exe"])
                                 .pick_file() 
                             {
                                 let path_str = path.to_string_lossy().to_string();
-                                self.state.game_path = Some(path_str.clone());
-                                self.state.config.game_path = Some(path_str);
-                                self.state.config.save();
+                                self.state.config.update(|config| config.game_path = Some(path_str));
                             }
                         }
                     });
@@ -501,60 +621,112 @@ impl DamageAnalyzer {
                     ui.separator();
 
                     ui.horizontal(|ui| {
-                        if ui.button("Launch").clicked() {
-                            if let Some(game_path) = &self.state.game_path {
+                        if ui.button(tr("Launch")).clicked() {
+                            if let (Some(game_path), Some(launcher)) = (&game_path, game_launcher()) {
                                 let dll_path = self.updater.get_dll_path();
-                                start_hijacked_process(game_path, dll_path.to_str().unwrap());
+                                launcher.launch(game_path, dll_path.to_str().unwrap());
                                 self.state.show_launcher = false;
                             }
                         }
 
-                        if ui.button("Cancel").clicked() {
+                        if ui.button(tr("Cancel")).clicked() {
                             self.state.show_launcher = false;
                         }
                     });
                 });
         }
 
+        if let Some(problem) = self.state.config.problem() {
+            egui::Window::new(tr("Settings Could Not Be Loaded"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr!("{} could not be read:", problem.path.display()));
+                    match &problem.field {
+                        Some(field) => ui.label(
+                            RichText::new(format!("{}: {}", field, problem.error)).color(ui.visuals().error_fg_color),
+                        ),
+                        None => ui.label(RichText::new(&problem.error).color(ui.visuals().error_fg_color)),
+                    };
+                    if let Some(backup_path) = &problem.backup_path {
+                        ui.label(tr!("A copy was saved to {}", backup_path.display()));
+                    }
+                    ui.label(tr("Default settings are in use and nothing will be saved until you choose:"));
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(problem.is_repairable(), egui::Button::new(tr("Repair")))
+                            .on_hover_text(tr("Keep every setting that can still be read"))
+                            .on_disabled_hover_text(tr("The file is not valid JSON"))
+                            .clicked()
+                        {
+                            let text = match self.state.config.repair() {
+                                Some(dropped) if dropped.is_empty() => tr("Settings repaired").to_string(),
+                                Some(dropped) => tr!("Settings repaired, reset: {}", dropped.join(", ")),
+                                None => tr("Settings could not be repaired").to_string(),
+                            };
+                            if let Ok(mut toast_lock) = self.toasts.try_lock() {
+                                toast_lock.add(Toast {
+                                    text: text.into(),
+                                    kind: ToastKind::Info,
+                                    options: ToastOptions::default().duration_in_seconds(5.0),
+                                    ..Default::default()
+                                });
+                            }
+                        }
+                        if ui.button(tr("Reset to Defaults")).clicked() {
+                            self.state.config.reset();
+                        }
+                        if ui.button(tr("Open Folder")).clicked() {
+                            if let Some(dir) = problem.path.parent() {
+                                let _ = open::that(dir);
+                            }
+                        }
+                    });
+                });
+        }
+
         if self.state.show_about {
-            egui::Window::new("About Veritas")
+            egui::Window::new(tr("About Veritas"))
                 .collapsible(false)
                 .resizable(false)
                 .min_width(400.0)
                 .show(ctx, |ui| {
                     ui.vertical_centered(|ui| {
                         ui.add_space(8.0);
-                        ui.heading("Veritas App Version");
-                        ui.label(format!("Version {}", env!("CARGO_PKG_VERSION")));
+                        ui.heading(tr("Veritas App Version"));
+                        ui.label(tr!("Version {}", env!("CARGO_PKG_VERSION")));
                         
                         if let Some(latest_app) = self.updater.latest_app_version() {
                             if latest_app != env!("CARGO_PKG_VERSION") {
-                                ui.label(RichText::new(format!("New version available: {}", latest_app)).color(egui::Color32::GREEN));
+                                ui.label(RichText::new(tr!("New version available: {}", latest_app)).color(egui::Color32::GREEN));
                             }
                         }
 
                         ui.add_space(16.0);
-                        ui.heading("Veritas Version");
+                        ui.heading(tr("Veritas Version"));
                         if let Some(dll_version) = self.updater.current_dll_version() {
-                            ui.label(format!("Current: {}", dll_version));
+                            ui.label(tr!("Current: {}", dll_version));
                             if let Some(latest_dll) = self.updater.latest_dll_version() {
                                 if latest_dll != dll_version {
-                                    ui.label(RichText::new(format!("New version available: {}", latest_dll)).color(egui::Color32::GREEN));
+                                    ui.label(RichText::new(tr!("New version available: {}", latest_dll)).color(egui::Color32::GREEN));
                                 }
                             }
                         } else {
-                            ui.label("Not installed");
+                            ui.label(tr("Not installed"));
                         }
 
                         ui.add_space(16.0);
-                        ui.heading("Source");
+                        ui.heading(tr("Source"));
                         ui.vertical_centered(|ui| {
                             ui.add(Hyperlink::new("https://github.com/NightKoneko/veritas-app"));
                             ui.add(Hyperlink::new("https://github.com/hessiser/veritas"));
                         });
 
                         ui.add_space(16.0);
-                        ui.heading("Developers");
+                        ui.heading(tr("Developers"));
                         ui.vertical_centered(|ui| {
                             ui.add(Hyperlink::from_label_and_url(
                                 "NightKoneko",
@@ -567,7 +739,7 @@ impl DamageAnalyzer {
                         });
 
                         ui.add_space(8.0);
-                        if ui.button("Close").clicked() {
+                        if ui.button(tr("Close")).clicked() {
                             self.state.show_about = false;
                         }
                         ui.add_space(8.0);
@@ -0,0 +1,237 @@
+use eframe::egui::{self, RichText, Ui};
+use rfd::FileDialog;
+use std::fs;
+use veritas_core::helpers::{CharacterColor, NumberFormat, Palette};
+use veritas_core::i18n::{tr, Language};
+use veritas_core::message_logger::LogLevel;
+use veritas_core::tr;
+
+use crate::app::{DamageAnalyzer, Unit};
+use crate::core::paths::{app_paths, PORTABLE_MARKER};
+use crate::core::preferences::{Preferences, Theme};
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum SettingsTab {
+    #[default]
+    General,
+    Charts,
+    Data,
+}
+
+impl DamageAnalyzer {
+    pub fn show_settings_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.state.show_preferences;
+        egui::Window::new(tr("Preferences"))
+            .id(egui::Id::new("preferences"))
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.state.settings_tab, SettingsTab::General, tr("General"));
+                    ui.selectable_value(&mut self.state.settings_tab, SettingsTab::Charts, tr("Charts"));
+                    ui.selectable_value(&mut self.state.settings_tab, SettingsTab::Data, tr("Data & Logs"));
+                });
+                ui.separator();
+
+                let before = self.state.config.read(|config| config.preferences.clone());
+                let mut preferences = before.clone();
+                match self.state.settings_tab {
+                    SettingsTab::General => show_general_tab(ui, &mut preferences),
+                    SettingsTab::Charts => {
+                        show_charts_tab(ui, &mut preferences);
+                        ui.separator();
+                        self.show_character_colors(ui, preferences.palette);
+                    }
+                    SettingsTab::Data => {
+                        show_log_level(ui, &mut preferences);
+                        ui.separator();
+                        self.show_damage_log_settings(ui);
+                    }
+                }
+                // Sliders and drag values change every frame they are dragged, so the
+                // edit is applied straight away but only saved once it settles
+                if preferences != before {
+                    self.state.config.preview(|config| config.preferences = preferences);
+                }
+
+                ui.separator();
+                if ui.button(tr("Restore Defaults")).clicked() {
+                    self.state.config.update(|config| config.preferences = Preferences::default());
+                }
+            });
+        self.state.show_preferences = open;
+    }
+
+    fn show_character_colors(&mut self, ui: &mut Ui, palette: Palette) {
+        ui.label(tr("Character colors:"));
+        let mut colors = self.state.config.read(|config| config.character_colors.clone());
+        if colors.0.is_empty() {
+            ui.label(RichText::new(tr("Characters get a color the first time they appear in a battle")).weak());
+            return;
+        }
+
+        let before = colors.clone();
+        let mut reset = None;
+        let mut forget = None;
+        egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+            egui::Grid::new("character_colors").num_columns(3).spacing([12.0, 4.0]).show(ui, |ui| {
+                for (name, color) in colors.0.iter_mut() {
+                    ui.label(name.as_str());
+                    let mut rgb = match *color {
+                        CharacterColor::Custom(rgb) => rgb,
+                        CharacterColor::Slot(slot) => palette.color(slot),
+                    };
+                    if ui.color_edit_button_srgb(&mut rgb).changed() {
+                        *color = CharacterColor::Custom(rgb);
+                    }
+                    ui.horizontal(|ui| {
+                        let is_custom = matches!(color, CharacterColor::Custom(_));
+                        if ui
+                            .add_enabled(is_custom, egui::Button::new(tr("Reset")))
+                            .on_hover_text(tr("Go back to a color from the palette"))
+                            .clicked()
+                        {
+                            reset = Some(name.clone());
+                        }
+                        if ui.button(tr("Forget")).on_hover_text(tr("Remove until the character is seen again")).clicked() {
+                            forget = Some(name.clone());
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        });
+
+        if let Some(name) = reset {
+            colors.0.remove(&name);
+            colors.assign(&[name]);
+        }
+        if let Some(name) = forget {
+            colors.0.remove(&name);
+        }
+        // The picker changes the color every frame it is dragged in
+        if colors != before {
+            self.state.config.preview(|config| config.character_colors = colors);
+        }
+    }
+
+    fn show_damage_log_settings(&mut self, ui: &mut Ui) {
+        let paths = app_paths();
+        let configured = self.state.config.read(|config| config.damage_log_dir.clone());
+        let damage_log_dir = paths.damage_log_dir(configured.as_deref());
+        ui.horizontal(|ui| {
+            ui.label(tr("Damage Logs:"));
+            ui.label(damage_log_dir.display().to_string());
+        });
+        ui.horizontal(|ui| {
+            if ui.button(tr("Browse")).clicked() {
+                if let Some(dir) = FileDialog::new().set_directory(&damage_log_dir).pick_folder() {
+                    self.state.config.update(|config| config.damage_log_dir = Some(dir));
+                }
+            }
+            if ui.add_enabled(configured.is_some(), egui::Button::new(tr("Use Default"))).clicked() {
+                self.state.config.update(|config| config.damage_log_dir = None);
+            }
+            if ui.button(tr("Open")).clicked() {
+                if let Err(e) = fs::create_dir_all(&damage_log_dir).and_then(|_| open::that(&damage_log_dir)) {
+                    log::error!("Failed to open {:?}: {}", damage_log_dir, e);
+                }
+            }
+        });
+        ui.label(
+            RichText::new(if paths.portable {
+                tr!("Portable mode: settings and data are kept in {}", paths.data_dir.display())
+            } else {
+                tr!("Place a {} file beside the executable for portable mode", PORTABLE_MARKER)
+            })
+            .weak(),
+        );
+    }
+}
+
+fn show_general_tab(ui: &mut Ui, preferences: &mut Preferences) {
+    ui.horizontal(|ui| {
+        ui.label(tr("Language:"));
+        egui::ComboBox::from_id_salt("language")
+            .selected_text(preferences.language.label())
+            .show_ui(ui, |ui| {
+                for language in Language::ALL {
+                    ui.selectable_value(&mut preferences.language, language, language.label());
+                }
+            });
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr("Theme:"));
+        for theme in Theme::ALL {
+            ui.radio_value(&mut preferences.theme, theme, tr(theme.label()));
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr("Compact mode opacity:"));
+        ui.add(egui::Slider::new(&mut preferences.compact_opacity, 0.3..=1.0).fixed_decimals(2));
+    });
+
+    ui.add_space(4.0);
+    ui.label(tr("On startup:"));
+    ui.checkbox(&mut preferences.restore_session, tr("Restore layout, pin and graph options from last session"));
+    ui.checkbox(&mut preferences.check_updates_on_startup, tr("Check for updates"));
+}
+
+fn show_charts_tab(ui: &mut Ui, preferences: &mut Preferences) {
+    egui::Grid::new("chart_settings").num_columns(2).spacing([12.0, 6.0]).show(ui, |ui| {
+        ui.label(tr("Default graph unit:"));
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut preferences.default_graph_unit, Unit::Turn, tr("Turn"));
+            ui.radio_value(&mut preferences.default_graph_unit, Unit::ActionValue, tr("Action Value"));
+        });
+        ui.end_row();
+
+        ui.label(tr("Number format:"));
+        egui::ComboBox::from_id_salt("number_format")
+            .selected_text(tr(preferences.number_format.label()))
+            .show_ui(ui, |ui| {
+                for format in NumberFormat::ALL {
+                    ui.selectable_value(&mut preferences.number_format, format, tr(format.label()));
+                }
+            });
+        ui.end_row();
+
+        ui.label(tr("Palette:"));
+        egui::ComboBox::from_id_salt("palette")
+            .selected_text(tr(preferences.palette.label()))
+            .show_ui(ui, |ui| {
+                for palette in Palette::ALL {
+                    ui.selectable_value(&mut preferences.palette, palette, tr(palette.label()));
+                }
+            });
+        ui.end_row();
+
+        let heights = &mut preferences.chart_heights;
+        for (label, height) in [
+            ("Damage over time height:", &mut heights.damage_over_time),
+            ("Distribution height:", &mut heights.distribution),
+            ("Total damage height:", &mut heights.totals),
+            ("DpAV height:", &mut heights.dpav),
+            ("Timeline height:", &mut heights.timeline),
+            ("Histogram height:", &mut heights.histogram),
+        ] {
+            ui.label(tr(label));
+            ui.add(egui::DragValue::new(height).range(100.0..=800.0).suffix(" px"));
+            ui.end_row();
+        }
+    });
+}
+
+fn show_log_level(ui: &mut Ui, preferences: &mut Preferences) {
+    ui.horizontal(|ui| {
+        ui.label(tr("Log verbosity:"));
+        egui::ComboBox::from_id_salt("log_verbosity")
+            .selected_text(tr(preferences.log_level.label()))
+            .show_ui(ui, |ui| {
+                for level in LogLevel::ALL {
+                    ui.selectable_value(&mut preferences.log_level, level, tr(level.label()));
+                }
+            });
+    });
+}
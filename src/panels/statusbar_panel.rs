@@ -1,17 +1,23 @@
 use eframe::egui::{self, Label};
 use egui_material_icons::icons::ICON_WIFI;
 
+use veritas_core::i18n::tr;
+use veritas_core::message_logger::{LogCategory, LogLevel};
+
 use crate::app::DamageAnalyzer;
+use crate::panels::compact_panel::COMPACT_SHORTCUT;
 
 impl DamageAnalyzer {
-    fn toggle_pin(&mut self) {
+    fn toggle_pin(&mut self, ctx: &egui::Context) {
         self.state.is_window_pinned = !self.state.is_window_pinned;
+        self.remember_session();
+        self.apply_window_level(ctx);
         let mut message_logger = self.message_logger.blocking_lock();
-        message_logger.log(if self.state.is_window_pinned {
+        message_logger.log(LogLevel::Info, LogCategory::App, if self.state.is_window_pinned {
             "Window pinned on top"
         } else {
             "Window unpinned"
-        });    
+        });
     }
 
     pub fn show_statusbar_panel(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
@@ -20,7 +26,7 @@ impl DamageAnalyzer {
             ui.horizontal(|ui| {
                 ui.style_mut().interaction.selectable_labels = false;
 
-                let connected = self.connected.blocking_lock().clone();
+                let connected = *self.connected.blocking_lock();
 
                 if connected {
                     ui.add(Label::new(egui_material_icons::icon_text(ICON_WIFI).color(egui::Color32::from_rgb(0, 180, 0))));
@@ -30,23 +36,30 @@ impl DamageAnalyzer {
 
                 ui.with_layout(egui::Layout::left_to_right(egui::Align::BOTTOM), |ui| {
                     ui.label(if connected {
-                        egui::RichText::new("Connected").color(egui::Color32::from_rgb(0, 180, 0))
+                        egui::RichText::new(tr("Connected")).color(egui::Color32::from_rgb(0, 180, 0))
                     } else {
                         // TODO: Make this not look terrible on light mode
-                        egui::RichText::new("Connecting...").color(egui::Color32::from_rgb(255, 180, 0))
+                        egui::RichText::new(tr("Connecting...")).color(egui::Color32::from_rgb(255, 180, 0))
                     });
                 });
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if ui
                         .button(if self.state.is_window_pinned {
-                            "Unpin Window"
+                            tr("Unpin Window")
                         } else {
-                            "Pin Window"
+                            tr("Pin Window")
                         })
                         .clicked()
                     {
-                        self.toggle_pin();
+                        self.toggle_pin(ctx);
+                    }
+                    if ui
+                        .button(tr("Compact Mode"))
+                        .on_hover_text(ctx.format_shortcut(&COMPACT_SHORTCUT))
+                        .clicked()
+                    {
+                        self.toggle_compact(ctx);
                     }
                 });
             });
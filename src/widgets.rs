@@ -1,5 +1,12 @@
+mod av_metrics_widget;
+mod av_timeline_widget;
+pub mod character_stats_widget;
 mod damage_bar_widget;
 mod damage_distribution_widget;
-mod log_widget;
+mod damage_heatmap_widget;
+mod dpav_plot_widget;
+mod hit_histogram_widget;
+pub mod log_widget;
 mod turn_damage_plot_widget;
-mod av_damage_plot_widget;
\ No newline at end of file
+mod turn_table_widget;
+mod av_damage_plot_widget;
@@ -1,45 +1,58 @@
 use eframe::egui::Ui;
-use egui_plot::{Legend, Line, Plot, PlotPoints};
+use egui_plot::{Legend, Plot};
+use veritas_core::i18n::tr;
 
-use crate::{app::DamageAnalyzer, core::helpers};
+use crate::{app::{DamageAnalyzer, GraphMode}, core::helpers};
 
 impl DamageAnalyzer {
     pub fn show_av_damage_plot_widget(&mut self, ui: &mut Ui) {
+        let (preferences, colors) = self.state.config.read(|config| {
+            (config.preferences.clone(), config.character_colors.clone())
+        });
+        let number_format = preferences.number_format;
+        let mode = self.state.graph_mode;
         Plot::new("dmg_av_plot")
             .legend(Legend::default())
-            .height(250.0)
+            .height(helpers::chart_height(ui, preferences.chart_heights.damage_over_time))
             .include_y(0.0)
-            .x_axis_label("Action Value")
-            .y_axis_label("Damage")
-            .y_axis_formatter(|y, _| helpers::format_damage(y.value))
+            .x_axis_label(tr("Action Value"))
+            .y_axis_label(tr(helpers::graph_axis_label(mode)))
+            .y_axis_formatter(move |y, _| match mode {
+                GraphMode::Share => format!("{:.0}%", y.value),
+                _ => number_format.format(y.value),
+            })
             .show(ui, |plot_ui| {
                 let data_buffer = self.data_buffer.blocking_lock().clone();
-                for (i, name) in data_buffer.column_names.iter().enumerate() {
-                    let color = helpers::get_character_color(i);
+                let series = data_buffer.column_names
+                    .iter()
+                    .map(|name| {
+                        data_buffer.av_damage
+                            .iter()
+                            .map(|dmg_map| dmg_map.get(name).copied().unwrap_or(0.0))
+                            .collect()
+                    })
+                    .collect();
+                let series = helpers::graph_series(series, mode);
 
-                    let av_damages = data_buffer.av_damage
-                        .iter()
-                        .map(|dmg_map| dmg_map.get(name).unwrap())
-                        .copied()
-                        .collect::<Vec<f64>>();
+                let mut lines: Vec<_> = data_buffer.column_names.iter().zip(series).collect();
+                if mode == GraphMode::Share {
+                    lines.reverse();
+                }
+                for (name, av_damages) in lines {
+                    let color = helpers::get_character_color(&colors, preferences.palette, name);
 
                     let points = data_buffer.av_history
                         .iter()
                         .zip(av_damages.iter())
-                        .map(|(x, y)| [*x as f64, *y as f64])
+                        .map(|(x, y)| [*x, *y])
                         .collect::<Vec<[f64; 2]>>();
 
-                    plot_ui.line(
-                        Line::new(PlotPoints::new(points))
-                            .name(name)
-                            .color(color)
-                            .width(2.0),
-                    );
+                    plot_ui.line(helpers::graph_line(points, name, color, mode));
                 }
-                let is_there_update = self.is_there_update.blocking_lock().clone();
+                let is_there_update = *self.is_there_update.blocking_lock();
                 if is_there_update {
                     plot_ui.set_auto_bounds([true, true]);
                 }
             });
     }
-}
\ No newline at end of file
+}
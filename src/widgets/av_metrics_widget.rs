@@ -0,0 +1,26 @@
+use eframe::egui::Ui;
+use veritas_core::i18n::tr;
+
+use crate::app::DamageAnalyzer;
+
+impl DamageAnalyzer {
+    pub fn show_av_metrics_widget(&mut self, ui: &mut Ui) {
+        let data_buffer = self.data_buffer.blocking_lock().clone();
+        let number_format = self.state.config.read(|config| config.preferences.number_format);
+        ui.style_mut().interaction.selectable_labels = false;
+
+        ui.label(tr("Current Turn"));
+        ui.horizontal(|ui| {
+            ui.label(tr("AV:"));
+            ui.label(format!("{:.2}", data_buffer.current_av));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Total Damage:"));
+            ui.label(number_format.format(data_buffer.total_damage.values().sum::<f64>()));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Total DpAV:"));
+            ui.label(number_format.format(data_buffer.total_dpav));
+        });
+    }
+}
@@ -0,0 +1,121 @@
+use eframe::egui::{Align2, Ui};
+use egui_plot::{uniform_grid_spacer, LineStyle, MarkerShape, Plot, PlotPoint, Points, Text, VLine};
+use veritas_core::i18n::tr;
+use veritas_core::tr;
+
+use crate::{app::DamageAnalyzer, core::helpers};
+
+/// The first cycle lasts 150 AV and every later one 100
+const FIRST_CYCLE_AV: f64 = 150.0;
+const CYCLE_AV: f64 = 100.0;
+
+impl DamageAnalyzer {
+    /// One lane per character with a marker at the AV of each of their turns
+    pub fn show_av_timeline_widget(&mut self, ui: &mut Ui) {
+        let data_buffer = self.data_buffer.blocking_lock().clone();
+        let (preferences, colors) = self.state.config.read(|config| {
+            (config.preferences.clone(), config.character_colors.clone())
+        });
+
+        // Lineup first, then anyone else who took a turn
+        let mut lanes = data_buffer.column_names.clone();
+        for owner in data_buffer.turn_records.iter().filter_map(|record| record.owner.as_ref()) {
+            if !lanes.contains(owner) {
+                lanes.push(owner.clone());
+            }
+        }
+        let max_av = data_buffer.turn_records
+            .iter()
+            .map(|record| record.action_value)
+            .fold(data_buffer.current_av, f64::max);
+        let axis_lanes = lanes.clone();
+        let cycle_color = ui.visuals().weak_text_color();
+        let wave_color = ui.visuals().warn_fg_color;
+        let other_color = ui.visuals().text_color();
+
+        Plot::new("av_timeline")
+            .height(helpers::chart_height(ui, preferences.chart_heights.timeline))
+            .include_x(0.0)
+            .include_y(0.5)
+            .include_y(0.5 - lanes.len() as f64)
+            .allow_zoom([true, false])
+            .allow_drag([true, false])
+            .allow_scroll([true, false])
+            .x_axis_label(tr("Action Value"))
+            .y_grid_spacer(uniform_grid_spacer(|_| [1.0, 1.0, 1.0]))
+            // Lanes run down from 0, so lane `i` sits at y = -i
+            .y_axis_formatter(move |y, _| {
+                let lane = -y.value;
+                if lane >= 0.0 && lane.fract() == 0.0 {
+                    axis_lanes.get(lane as usize).cloned().unwrap_or_default()
+                } else {
+                    String::new()
+                }
+            })
+            .label_formatter(|name, value| {
+                if name.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}\n{} {:.2}", name, tr("AV"), value.x)
+                }
+            })
+            .show(ui, |plot_ui| {
+                // Cycles count from 0, like the ones the game reports
+                let mut cycle_start = 0.0;
+                let mut cycle = 0;
+                while cycle_start <= max_av {
+                    if cycle > 0 {
+                        plot_ui.vline(VLine::new(cycle_start).color(cycle_color).style(LineStyle::dashed_loose()));
+                    }
+                    plot_ui.text(
+                        Text::new(PlotPoint::new(cycle_start, 0.5), tr!("Cycle {}", cycle))
+                            .color(cycle_color)
+                            .anchor(Align2::LEFT_TOP),
+                    );
+                    cycle_start = FIRST_CYCLE_AV + CYCLE_AV * cycle as f64;
+                    cycle += 1;
+                }
+
+                let mut wave = None;
+                for record in &data_buffer.turn_records {
+                    if wave.is_some_and(|wave| wave != record.wave) {
+                        plot_ui.vline(VLine::new(record.action_value).color(wave_color).width(1.5_f32));
+                        plot_ui.text(
+                            Text::new(
+                                PlotPoint::new(record.action_value, 0.5 - lanes.len() as f64),
+                                tr!("Wave {}", record.wave),
+                            )
+                            .color(wave_color)
+                            .anchor(Align2::LEFT_BOTTOM),
+                        );
+                    }
+                    wave = Some(record.wave);
+                }
+
+                for (i, name) in lanes.iter().enumerate() {
+                    let points: Vec<[f64; 2]> = data_buffer.turn_records
+                        .iter()
+                        .filter(|record| record.owner.as_ref() == Some(name))
+                        .map(|record| [record.action_value, -(i as f64)])
+                        .collect();
+                    let color = if data_buffer.column_names.contains(name) {
+                        helpers::get_character_color(&colors, preferences.palette, name)
+                    } else {
+                        other_color
+                    };
+                    plot_ui.points(
+                        Points::new(points)
+                            .name(name)
+                            .color(color)
+                            .shape(MarkerShape::Diamond)
+                            .radius(5.0_f32),
+                    );
+                }
+
+                let is_there_update = *self.is_there_update.blocking_lock();
+                if is_there_update {
+                    plot_ui.set_auto_bounds([true, true]);
+                }
+            });
+    }
+}
@@ -0,0 +1,180 @@
+use eframe::egui::{self, Align, Layout, Ui};
+use egui_extras::{Column, TableBuilder};
+use egui_material_icons::icons::{ICON_ARROW_DOWNWARD, ICON_ARROW_UPWARD};
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use veritas_core::helpers::NumberFormat;
+use veritas_core::i18n::tr;
+use veritas_core::stats::{self, CharacterStats};
+
+use crate::app::DamageAnalyzer;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatsColumn {
+    Name,
+    Damage,
+    Share,
+    Hits,
+    MeanHit,
+    MedianHit,
+    MaxHit,
+    Actions,
+    DamagePerAction,
+    Kills,
+    DamagePerAv,
+}
+
+impl StatsColumn {
+    const ALL: [StatsColumn; 11] = [
+        StatsColumn::Name,
+        StatsColumn::Damage,
+        StatsColumn::Share,
+        StatsColumn::Hits,
+        StatsColumn::MeanHit,
+        StatsColumn::MedianHit,
+        StatsColumn::MaxHit,
+        StatsColumn::Actions,
+        StatsColumn::DamagePerAction,
+        StatsColumn::Kills,
+        StatsColumn::DamagePerAv,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            StatsColumn::Name => "Character",
+            StatsColumn::Damage => "Damage",
+            StatsColumn::Share => "Share",
+            StatsColumn::Hits => "Hits",
+            StatsColumn::MeanHit => "Mean Hit",
+            StatsColumn::MedianHit => "Median Hit",
+            StatsColumn::MaxHit => "Max Hit",
+            StatsColumn::Actions => "Actions",
+            StatsColumn::DamagePerAction => "Damage per Action",
+            StatsColumn::Kills => "Kills",
+            StatsColumn::DamagePerAv => "DpAV",
+        }
+    }
+
+    /// Sort key of every column but `Name`
+    fn value(self, stats: &CharacterStats) -> f64 {
+        match self {
+            StatsColumn::Name => 0.0,
+            StatsColumn::Damage => stats.total_damage,
+            StatsColumn::Share => stats.share,
+            StatsColumn::Hits => stats.hits as f64,
+            StatsColumn::MeanHit => stats.mean_hit,
+            StatsColumn::MedianHit => stats.median_hit,
+            StatsColumn::MaxHit => stats.max_hit,
+            StatsColumn::Actions => stats.actions as f64,
+            StatsColumn::DamagePerAction => stats.damage_per_action,
+            StatsColumn::Kills => stats.kills as f64,
+            StatsColumn::DamagePerAv => stats.damage_per_av,
+        }
+    }
+
+    fn text(self, stats: &CharacterStats, numbers: NumberFormat) -> String {
+        match self {
+            StatsColumn::Name => stats.name.clone(),
+            StatsColumn::Share => format!("{:.1}%", stats.share * 100.0),
+            StatsColumn::Hits => stats.hits.to_string(),
+            StatsColumn::Actions => stats.actions.to_string(),
+            StatsColumn::Kills => stats.kills.to_string(),
+            column => numbers.format(column.value(stats)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct StatsSort {
+    pub column: StatsColumn,
+    pub descending: bool,
+}
+
+impl Default for StatsSort {
+    fn default() -> Self {
+        Self {
+            column: StatsColumn::Damage,
+            descending: true,
+        }
+    }
+}
+
+impl DamageAnalyzer {
+    pub fn show_character_stats_widget(&mut self, ui: &mut Ui) {
+        let data_buffer = self.data_buffer.blocking_lock().clone();
+        let number_format = self.state.config.read(|config| config.preferences.number_format);
+        let sort = self.state.stats_sort;
+
+        let mut rows = CharacterStats::from_battle(&data_buffer);
+        rows.sort_by(|a, b| {
+            let ordering = match sort.column {
+                StatsColumn::Name => a.name.cmp(&b.name),
+                column => column.value(a).total_cmp(&column.value(b)),
+            };
+            if sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        if ui.button(tr("Copy as TSV")).clicked() {
+            ui.ctx().copy_text(stats::to_tsv(&rows));
+            if let Ok(mut toasts) = self.toasts.try_lock() {
+                toasts.add(Toast {
+                    text: tr("Copied statistics to the clipboard").into(),
+                    kind: ToastKind::Info,
+                    options: ToastOptions::default().duration_in_seconds(3.0),
+                    ..Default::default()
+                });
+            }
+        }
+
+        egui::ScrollArea::horizontal().id_salt("character_stats_scroll").show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt("character_stats")
+                .striped(true)
+                .resizable(true)
+                .column(Column::auto().at_least(80.0))
+                .columns(Column::auto().at_least(60.0), StatsColumn::ALL.len() - 1)
+                .header(20.0, |mut header| {
+                    for column in StatsColumn::ALL {
+                        header.col(|ui| {
+                            let arrow = match (sort.column == column, sort.descending) {
+                                (true, true) => ICON_ARROW_DOWNWARD,
+                                (true, false) => ICON_ARROW_UPWARD,
+                                _ => "",
+                            };
+                            let text = format!("{} {}", tr(column.label()), arrow);
+                            if ui.selectable_label(sort.column == column, text.trim_end()).clicked() {
+                                self.state.stats_sort = StatsSort {
+                                    column,
+                                    // Names read best A to Z, numbers largest first
+                                    descending: if sort.column == column {
+                                        !sort.descending
+                                    } else {
+                                        column != StatsColumn::Name
+                                    },
+                                };
+                            }
+                        });
+                    }
+                })
+                .body(|mut body| {
+                    for stats in &rows {
+                        body.row(18.0, |mut row| {
+                            for column in StatsColumn::ALL {
+                                row.col(|ui| {
+                                    let text = column.text(stats, number_format);
+                                    if column == StatsColumn::Name {
+                                        ui.label(text);
+                                    } else {
+                                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| ui.label(text));
+                                    }
+                                });
+                            }
+                        });
+                    }
+                });
+        });
+    }
+}
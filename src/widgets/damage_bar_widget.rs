@@ -3,14 +3,13 @@ use egui_plot::{Bar, BarChart, Legend, Plot};
 
 use crate::app::DamageAnalyzer;
 use crate::core::helpers;
-use crate::core::models::DataBufferInner;
+use crate::core::models::BattleData;
 
-fn create_bar_data(buffer: &DataBufferInner) -> Vec<(String, f64, usize)> {        
+fn create_bar_data(buffer: &BattleData) -> Vec<(String, f64)> {        
     buffer.column_names.iter()
-        .enumerate()
-        .filter_map(|(i, name)| {
+        .filter_map(|name| {
             buffer.total_damage.get(name)
-                .map(|&damage| (name.clone(), damage as f64, i))
+                .map(|&damage| (name.clone(), damage))
         })
         .collect()
 }
@@ -18,17 +17,21 @@ fn create_bar_data(buffer: &DataBufferInner) -> Vec<(String, f64, usize)> {
 impl DamageAnalyzer {
     pub fn show_damage_bar_widget(&mut self, ui: &mut Ui) {
         let data_buffer = self.data_buffer.blocking_lock().clone();
+        let (preferences, colors) = self.state.config.read(|config| {
+            (config.preferences.clone(), config.character_colors.clone())
+        });
+        let number_format = preferences.number_format;
         Plot::new("damage_bars")
             .legend(Legend::default())
-            .height(300.0)
+            .height(helpers::chart_height(ui, preferences.chart_heights.totals))
             .width(ui.available_width())
             .allow_drag(false)
             .allow_zoom(false)
             .allow_scroll(false)
-            .y_axis_formatter(|y, _| helpers::format_damage(y.value))
+            .y_axis_formatter(move |y, _| number_format.format(y.value))
             .x_axis_formatter(|x, _| {
                 let bars_data = create_bar_data(&data_buffer);
-                if let Some((name, _, _)) = bars_data.get(x.value.floor() as usize) {
+                if let Some((name, _)) = bars_data.get(x.value.floor() as usize) {
                     return name.clone();
                 }
                 String::new()
@@ -40,10 +43,10 @@ impl DamageAnalyzer {
                 let bars: Vec<Bar> = bars_data
                     .iter()
                     .enumerate()
-                    .map(|(pos, (name, value, color_idx))| {
+                    .map(|(pos, (name, value))| {
                         Bar::new(pos as f64, *value)
                             .name(name)
-                            .fill(helpers::get_character_color(*color_idx))
+                            .fill(helpers::get_character_color(&colors, preferences.palette, name))
                             .width(0.7)
                     })
                     .collect();
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use eframe::egui::{Stroke, Ui};
 use egui_plot::{Legend, Plot, PlotPoints, Polygon};
+use veritas_core::tr;
 
 use crate::{app::DamageAnalyzer, core::helpers};
 
@@ -11,21 +12,21 @@ pub struct PieSegment {
     pub value: f64,
 }
 
-fn create_pie_segments(damage_map: &HashMap<String, f64>, column_names: &[String]) -> Vec<(String, PieSegment, usize)> {
-    let total: f64 = damage_map.values().sum::<f64>() as f64;
+fn create_pie_segments(damage_map: &HashMap<String, f64>, column_names: &[String]) -> Vec<(String, PieSegment)> {
+    let total: f64 = damage_map.values().sum::<f64>();
     let mut segments = Vec::new();
     let mut start_angle = -std::f64::consts::FRAC_PI_2; 
 
-    for (i, name) in column_names.iter().enumerate() {
+    for name in column_names {
         if let Some(&damage) = damage_map.get(name) {
-            let fraction = damage as f64 / total;
+            let fraction = damage / total;
             let angle = fraction * std::f64::consts::TAU;
             let end_angle = start_angle + angle;
 
             segments.push((name.clone(), PieSegment {
                 points: create_pie_slice(start_angle, end_angle),
-                value: damage as f64,
-            }, i));
+                value: damage,
+            }));
 
             start_angle = end_angle;
         }
@@ -54,10 +55,13 @@ fn create_pie_slice(start_angle: f64, end_angle: f64) -> Vec<[f64; 2]> {
 impl DamageAnalyzer {
     pub fn show_damage_distribution_widget(&mut self, ui: &mut Ui) {
         let data_buffer = self.data_buffer.blocking_lock().clone();
+        let (preferences, colors) = self.state.config.read(|config| {
+            (config.preferences.clone(), config.character_colors.clone())
+        });
         Plot::new("damage_pie")
             .legend(Legend::default().position(egui_plot::Corner::RightTop))
-            .height(300.0)
-            .width(ui.available_width() * 0.5)
+            .height(helpers::chart_height(ui, preferences.chart_heights.distribution))
+            .width(ui.available_width())
             .data_aspect(1.0)
             .clamp_grid(true)
             .show_grid(false)
@@ -67,22 +71,22 @@ impl DamageAnalyzer {
             .allow_zoom(false)
             .allow_scroll(false)
             .show(ui, |plot_ui: &mut egui_plot::PlotUi<'_>| {
-                let total: f64 = data_buffer.total_damage.values().sum::<f64>() as f64;
+                let total: f64 = data_buffer.total_damage.values().sum::<f64>();
                 if total > 0.0 {
                     let segments =
                         create_pie_segments(&data_buffer.total_damage, &data_buffer.column_names);
-                    for (name, segment, i) in segments {
-                        let color = helpers::get_character_color(i);
+                    for (name, segment) in segments {
+                        let color = helpers::get_character_color(&colors, preferences.palette, &name);
                         let percentage = segment.value / total * 100.0;
 
                         let plot_points = PlotPoints::new(segment.points);
                         let polygon = Polygon::new(plot_points)
-                            .stroke(Stroke::new(1.5, color))
-                            .name(format!(
-                                "{}: {:.1}% ({} dmg)",
+                            .stroke(Stroke::new(1.5_f32, color))
+                            .name(tr!(
+                                "{}: {}% ({} dmg)",
                                 name,
-                                percentage,
-                                helpers::format_damage(segment.value)
+                                format!("{:.1}", percentage),
+                                preferences.number_format.format(segment.value)
                             ));
 
                         plot_ui.polygon(polygon);
@@ -0,0 +1,151 @@
+use std::collections::{BTreeMap, HashMap};
+
+use eframe::egui::{self, Align2, Sense, TextStyle, Ui, Vec2};
+use veritas_core::i18n::tr;
+
+use crate::app::{DamageAnalyzer, Unit};
+use crate::core::helpers;
+
+const CELL_WIDTH: f32 = 18.0;
+const ROW_HEIGHT: f32 = 20.0;
+/// Width of each column when the heatmap is by action value
+const AV_BUCKET: f64 = 50.0;
+
+/// A heatmap column: its header, what its tooltips call it and each character's damage in it
+struct HeatmapColumn {
+    label: String,
+    title: String,
+    damage: HashMap<String, f64>,
+}
+
+fn turn_columns(turn_damage: &[HashMap<String, f64>]) -> Vec<HeatmapColumn> {
+    turn_damage
+        .iter()
+        .enumerate()
+        .map(|(i, damage)| HeatmapColumn {
+            label: (i + 1).to_string(),
+            title: format!("{} {}", tr("Turn"), i + 1),
+            damage: damage.clone(),
+        })
+        .collect()
+}
+
+/// Sums the damage at each AV into [`AV_BUCKET`] wide columns, keeping empty ones in between
+fn av_columns(av_history: &[f64], av_damage: &[HashMap<String, f64>]) -> Vec<HeatmapColumn> {
+    let mut buckets: BTreeMap<i64, HashMap<String, f64>> = BTreeMap::new();
+    for (av, damage) in av_history.iter().zip(av_damage) {
+        let bucket = buckets.entry((av / AV_BUCKET).floor() as i64).or_default();
+        for (name, value) in damage {
+            *bucket.entry(name.clone()).or_insert(0.0) += value;
+        }
+    }
+    let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) else {
+        return Vec::new();
+    };
+    (first..=last)
+        .map(|bucket| {
+            let start = bucket as f64 * AV_BUCKET;
+            HeatmapColumn {
+                label: format!("{:.0}", start),
+                title: format!("{} {:.0}–{:.0}", tr("AV"), start, start + AV_BUCKET),
+                damage: buckets.remove(&bucket).unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+impl DamageAnalyzer {
+    /// Characters down, turns or AV across, each cell shaded by the damage in it
+    pub fn show_damage_heatmap_widget(&mut self, ui: &mut Ui) {
+        let data_buffer = self.data_buffer.blocking_lock().clone();
+        let (preferences, colors) = self.state.config.read(|config| {
+            (config.preferences.clone(), config.character_colors.clone())
+        });
+        let number_format = preferences.number_format;
+
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.state.heatmap_unit, Unit::Turn, tr("Turn"));
+            ui.radio_value(&mut self.state.heatmap_unit, Unit::ActionValue, tr("Action Value"));
+        });
+        let columns = match self.state.heatmap_unit {
+            Unit::Turn => turn_columns(&data_buffer.turn_damage),
+            Unit::ActionValue => av_columns(&data_buffer.av_history, &data_buffer.av_damage),
+        };
+        let names = &data_buffer.column_names;
+        let max = columns
+            .iter()
+            .flat_map(|column| column.damage.values())
+            .fold(0.0_f64, |max, &value| max.max(value));
+
+        let font = TextStyle::Small.resolve(ui.style());
+        let text_color = ui.visuals().text_color();
+        let empty_color = ui.visuals().extreme_bg_color;
+        let label_width = names
+            .iter()
+            .map(|name| ui.fonts(|fonts| fonts.layout_no_wrap(name.clone(), font.clone(), text_color).size().x))
+            .fold(0.0_f32, f32::max)
+            + 8.0;
+        // Leave room between header labels when the columns are narrower than the text
+        let header_step = columns
+            .last()
+            .map(|column| {
+                ui.fonts(|fonts| fonts.layout_no_wrap(column.label.clone(), font.clone(), text_color).size().x)
+            })
+            .map_or(1, |width| ((width + 4.0) / CELL_WIDTH).ceil() as usize);
+
+        egui::ScrollArea::horizontal().id_salt("damage_heatmap_scroll").show(ui, |ui| {
+            let size = Vec2::new(
+                label_width + columns.len() as f32 * CELL_WIDTH,
+                ROW_HEIGHT * (names.len() + 1) as f32,
+            );
+            let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+            let painter = ui.painter_at(rect);
+            let cell_origin = rect.min + Vec2::new(label_width, ROW_HEIGHT);
+
+            for (i, column) in columns.iter().enumerate().step_by(header_step) {
+                let x = cell_origin.x + i as f32 * CELL_WIDTH;
+                let position = egui::pos2(x, rect.top() + ROW_HEIGHT / 2.0);
+                painter.text(position, Align2::LEFT_CENTER, &column.label, font.clone(), text_color);
+            }
+            for (row, name) in names.iter().enumerate() {
+                let y = cell_origin.y + row as f32 * ROW_HEIGHT;
+                let position = egui::pos2(rect.left(), y + ROW_HEIGHT / 2.0);
+                painter.text(position, Align2::LEFT_CENTER, name, font.clone(), text_color);
+                let color = helpers::get_character_color(&colors, preferences.palette, name);
+                for (i, column) in columns.iter().enumerate() {
+                    let value = column.damage.get(name).copied().unwrap_or(0.0);
+                    // Square root so small turns still show against one huge one
+                    let t = if max > 0.0 { (value / max).sqrt() as f32 } else { 0.0 };
+                    let cell = egui::Rect::from_min_size(
+                        egui::pos2(cell_origin.x + i as f32 * CELL_WIDTH, y),
+                        Vec2::new(CELL_WIDTH, ROW_HEIGHT),
+                    )
+                    .shrink(1.0);
+                    painter.rect_filled(cell, 1.0, empty_color.lerp_to_gamma(color, t));
+                }
+            }
+
+            let Some(pointer) = response.hover_pos() else {
+                return;
+            };
+            let offset = pointer - cell_origin;
+            if offset.x < 0.0 || offset.y < 0.0 {
+                return;
+            }
+            let (i, row) = ((offset.x / CELL_WIDTH) as usize, (offset.y / ROW_HEIGHT) as usize);
+            if let (Some(column), Some(name)) = (columns.get(i), names.get(row)) {
+                let value = column.damage.get(name).copied().unwrap_or(0.0);
+                let column_total: f64 = column.damage.values().sum();
+                response.on_hover_ui_at_pointer(|ui| {
+                    ui.strong(name);
+                    ui.label(&column.title);
+                    ui.label(format!(
+                        "{} ({:.1}%)",
+                        number_format.format(value),
+                        if column_total > 0.0 { value / column_total * 100.0 } else { 0.0 }
+                    ));
+                });
+            }
+        });
+    }
+}
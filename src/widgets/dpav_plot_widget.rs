@@ -0,0 +1,34 @@
+use eframe::egui::Ui;
+use egui_plot::{Line, Plot, PlotPoints};
+use veritas_core::i18n::tr;
+
+use crate::{app::DamageAnalyzer, core::helpers};
+
+impl DamageAnalyzer {
+    pub fn show_dpav_plot_widget(&mut self, ui: &mut Ui) {
+        let data_buffer = self.data_buffer.blocking_lock().clone();
+        let preferences = self.state.config.read(|config| config.preferences.clone());
+        let number_format = preferences.number_format;
+        Plot::new("dpav_plot")
+            .height(helpers::chart_height(ui, preferences.chart_heights.dpav))
+            .include_y(0.0)
+            .allow_drag(false)
+            .allow_zoom(false)
+            .x_axis_label(tr("Turn"))
+            .y_axis_label(tr("DpAV"))
+            .y_axis_formatter(move |y, _| number_format.format(y.value))
+            .show(ui, |plot_ui| {
+                let points: Vec<[f64; 2]> = data_buffer
+                    .dpav_history
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &dpav)| [i as f64 + 1.0, dpav])
+                    .collect();
+                plot_ui.line(
+                    Line::new(PlotPoints::from(points))
+                        .name(tr("DpAV"))
+                        .width(2.0_f32),
+                );
+            });
+    }
+}
@@ -0,0 +1,111 @@
+use eframe::egui::{self, Ui};
+use egui_plot::{Bar, BarChart, Legend, LineStyle, Plot, VLine};
+use veritas_core::i18n::tr;
+use veritas_core::stats;
+
+use crate::{app::DamageAnalyzer, core::helpers};
+
+/// Bins are evenly spaced in log10(damage), this many to each power of ten
+const BINS_PER_DECADE: f64 = 5.0;
+const PERCENTILES: [f64; 3] = [0.5, 0.9, 0.99];
+
+fn bin(damage: f64) -> i32 {
+    (damage.log10() * BINS_PER_DECADE).floor() as i32
+}
+
+impl DamageAnalyzer {
+    /// Hit sizes on a log scale, stacked by character, with percentile markers
+    pub fn show_hit_histogram_widget(&mut self, ui: &mut Ui) {
+        let data_buffer = self.data_buffer.blocking_lock().clone();
+        let (preferences, colors) = self.state.config.read(|config| {
+            (config.preferences.clone(), config.character_colors.clone())
+        });
+        let number_format = preferences.number_format;
+
+        let selected = &mut self.state.histogram_character;
+        if selected.as_ref().is_some_and(|name| !data_buffer.column_names.contains(name)) {
+            *selected = None;
+        }
+        egui::ComboBox::from_id_salt("histogram_character")
+            .selected_text(selected.as_deref().unwrap_or(tr("All Characters")))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(selected, None, tr("All Characters"));
+                for name in &data_buffer.column_names {
+                    ui.selectable_value(selected, Some(name.clone()), name);
+                }
+            });
+
+        let names: Vec<&String> = data_buffer.column_names
+            .iter()
+            .filter(|name| selected.as_ref().is_none_or(|selected| selected == *name))
+            .collect();
+        let hits: Vec<(&String, f64)> = data_buffer.hits
+            .iter()
+            .filter(|hit| hit.damage > 0.0 && names.contains(&&hit.attacker))
+            .map(|hit| (&hit.attacker, hit.damage))
+            .collect();
+        let mut sorted: Vec<f64> = hits.iter().map(|(_, damage)| *damage).collect();
+        sorted.sort_by(f64::total_cmp);
+
+        // Every chart covers the same bins so they stack bar for bar
+        let bins = sorted.first().map_or(0, |&min| bin(min))..=sorted.last().map_or(-1, |&max| bin(max));
+        let mut charts: Vec<BarChart> = Vec::new();
+        for name in &names {
+            let bars = bins
+                .clone()
+                .map(|bin_index| {
+                    let count = hits
+                        .iter()
+                        .filter(|(attacker, damage)| attacker == name && bin(*damage) == bin_index)
+                        .count();
+                    Bar::new((bin_index as f64 + 0.5) / BINS_PER_DECADE, count as f64)
+                        .width(0.9 / BINS_PER_DECADE)
+                })
+                .collect();
+            let chart = BarChart::new(bars)
+                .name(name)
+                .color(helpers::get_character_color(&colors, preferences.palette, name));
+            let chart = {
+                let below: Vec<&BarChart> = charts.iter().collect();
+                chart.stack_on(&below)
+            };
+            charts.push(chart);
+        }
+
+        let marker_color = ui.visuals().text_color();
+        Plot::new("hit_histogram")
+            .legend(Legend::default())
+            .height(helpers::chart_height(ui, preferences.chart_heights.histogram))
+            .include_y(0.0)
+            .x_axis_label(tr("Hit Damage"))
+            .y_axis_label(tr("Hits"))
+            .x_axis_formatter(move |x, _| number_format.format(10f64.powf(x.value)))
+            .label_formatter(move |name, value| {
+                if name.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}\n{}", name, number_format.format(10f64.powf(value.x)))
+                }
+            })
+            .show(ui, |plot_ui| {
+                for chart in charts {
+                    plot_ui.bar_chart(chart);
+                }
+                if !sorted.is_empty() {
+                    for p in PERCENTILES {
+                        let value = stats::percentile(&sorted, p);
+                        plot_ui.vline(
+                            VLine::new(value.log10())
+                                .name(format!("P{:.0}", p * 100.0))
+                                .color(marker_color)
+                                .style(LineStyle::dashed_loose()),
+                        );
+                    }
+                }
+                let is_there_update = *self.is_there_update.blocking_lock();
+                if is_there_update {
+                    plot_ui.set_auto_bounds([true, true]);
+                }
+            });
+    }
+}
@@ -1,23 +1,153 @@
-use eframe::egui::{self, Ui};
+use std::collections::HashSet;
+
+use eframe::egui::{self, Color32, RichText, Ui};
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use rfd::FileDialog;
+use veritas_core::i18n::tr;
+use veritas_core::message_logger::{LogCategory, LogEntry, LogLevel};
+use veritas_core::tr;
 
 use crate::app::DamageAnalyzer;
 
+/// Filters and scroll state of the log panel
+#[derive(Clone)]
+pub struct LogView {
+    pub min_level: LogLevel,
+    pub hidden_categories: HashSet<LogCategory>,
+    pub search: String,
+    pub autoscroll: bool,
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self {
+            min_level: LogLevel::Info,
+            hidden_categories: HashSet::new(),
+            search: String::new(),
+            autoscroll: true,
+        }
+    }
+}
+
+impl LogView {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        entry.level >= self.min_level
+            && !self.hidden_categories.contains(&entry.category)
+            && (self.search.is_empty()
+                || entry.message.to_lowercase().contains(&self.search.to_lowercase()))
+    }
+}
+
+fn level_color(level: LogLevel, ui: &Ui) -> Color32 {
+    match level {
+        LogLevel::Debug => ui.visuals().weak_text_color(),
+        LogLevel::Info => ui.visuals().text_color(),
+        LogLevel::Warn => ui.visuals().warn_fg_color,
+        LogLevel::Error => ui.visuals().error_fg_color,
+    }
+}
+
 impl DamageAnalyzer {
     pub fn show_log_widget(&mut self, ui: &mut Ui) {
-        ui.heading("Logs");
-        let message_logger = self.message_logger.blocking_lock().clone();
-        let text = message_logger.get_text();
+        let view = &mut self.state.log_view;
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("log_level")
+                .selected_text(tr(view.min_level.label()))
+                .show_ui(ui, |ui| {
+                    for level in LogLevel::ALL {
+                        ui.selectable_value(&mut view.min_level, level, tr(level.label()));
+                    }
+                });
+            ui.checkbox(&mut view.autoscroll, tr("Autoscroll"));
+        });
+        ui.horizontal_wrapped(|ui| {
+            for category in LogCategory::ALL {
+                let mut shown = !view.hidden_categories.contains(&category);
+                if ui.toggle_value(&mut shown, tr(category.label())).changed() {
+                    if shown {
+                        view.hidden_categories.remove(&category);
+                    } else {
+                        view.hidden_categories.insert(category);
+                    }
+                }
+            }
+        });
+        let (clear, export) = ui
+            .horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut view.search)
+                        .hint_text(tr("Search"))
+                        .desired_width(ui.available_width() - 120.0),
+                );
+                (ui.button(tr("Clear")).clicked(), ui.button(tr("Export...")).clicked())
+            })
+            .inner;
+        ui.separator();
+
+        if clear {
+            self.message_logger.blocking_lock().clear();
+        }
+        if export {
+            self.export_log();
+        }
+
+        let view = &self.state.log_view;
+        let entries: Vec<LogEntry> = self
+            .message_logger
+            .blocking_lock()
+            .entries()
+            .filter(|entry| view.matches(entry))
+            .cloned()
+            .collect();
+
         egui::ScrollArea::vertical()
-            .stick_to_bottom(true)
+            .stick_to_bottom(view.autoscroll)
+            .auto_shrink([false, false])
             .max_height(ui.available_height() - 10.0)
-            .show(ui, |ui| {
-                let _response = ui.add(
-                    egui::TextEdit::multiline(&mut text.as_str())
-                        .desired_width(f32::INFINITY)
-                        .desired_rows(1)
-                        .frame(false)
-                        .margin(egui::vec2(2.0, 2.0)),
-                );
+            .show_rows(ui, ui.text_style_height(&egui::TextStyle::Monospace), entries.len(), |ui, range| {
+                for entry in &entries[range] {
+                    // Rows must keep a fixed height for `show_rows`, long lines show in full on hover
+                    ui.add(
+                        egui::Label::new(
+                            RichText::new(entry.to_string())
+                                .monospace()
+                                .color(level_color(entry.level, ui)),
+                        )
+                        .truncate(),
+                    );
+                }
+            });
+    }
+
+    /// Saves the entries that pass the current filters
+    fn export_log(&mut self) {
+        let Some(path) = FileDialog::new()
+            .add_filter(tr("Log"), &["log", "txt"])
+            .set_file_name(format!("veritas_{}.log", chrono::Local::now().format("%Y%m%d_%H%M%S")))
+            .save_file()
+        else {
+            return;
+        };
+
+        let text = self
+            .message_logger
+            .blocking_lock()
+            .entries()
+            .filter(|entry| self.state.log_view.matches(entry))
+            .map(|entry| format!("{}\n", entry))
+            .collect::<String>();
+
+        let (text, kind) = match std::fs::write(&path, text) {
+            Ok(()) => (tr!("Exported log to {}", path.display()), ToastKind::Success),
+            Err(e) => (tr!("Failed to export log: {}", e), ToastKind::Error),
+        };
+        if let Ok(mut toast_lock) = self.toasts.try_lock() {
+            toast_lock.add(Toast {
+                text: text.into(),
+                kind,
+                options: ToastOptions::default().duration_in_seconds(3.0),
+                ..Default::default()
             });
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,42 +1,69 @@
 use eframe::egui::Ui;
-use egui_plot::{Legend, Line, Plot, PlotPoints};
+use egui_plot::{Legend, Plot};
+use veritas_core::i18n::tr;
 
-use crate::{app::DamageAnalyzer, core::helpers};
+use crate::{app::{DamageAnalyzer, GraphMode}, core::helpers};
 
 impl DamageAnalyzer {
     pub fn show_turn_damage_plot_widget(&mut self, ui: &mut Ui) {
         let data_buffer = self.data_buffer.blocking_lock().clone();
+        let (preferences, colors) = self.state.config.read(|config| {
+            (config.preferences.clone(), config.character_colors.clone())
+        });
+        let number_format = preferences.number_format;
+        let mode = self.state.graph_mode;
         Plot::new("damage_plot")
             .legend(Legend::default())
-            .height(250.0)
+            .height(helpers::chart_height(ui, preferences.chart_heights.damage_over_time))
             .include_y(0.0)
-            .x_axis_label("Turn")
-            .y_axis_label("Damage")
-            .y_axis_formatter(|y, _| helpers::format_damage(y.value))
+            .x_axis_label(tr("Turn"))
+            .y_axis_label(tr(helpers::graph_axis_label(mode)))
+            .y_axis_formatter(move |y, _| match mode {
+                GraphMode::Share => format!("{:.0}%", y.value),
+                _ => number_format.format(y.value),
+            })
             .show(ui, |plot_ui| {
-                for (i, name) in data_buffer.column_names.iter().enumerate() {
-                    let color = helpers::get_character_color(i);
-                    let damage_points = &data_buffer.turn_damage
+                let series = data_buffer.column_names
+                    .iter()
+                    .map(|name| {
+                        data_buffer.turn_damage
+                            .iter()
+                            .map(|dmg_map| dmg_map.get(name).copied().unwrap_or(0.0))
+                            .collect()
+                    })
+                    .collect();
+                let series = helpers::graph_series(series, mode);
+
+                let mut lines: Vec<_> = data_buffer.column_names.iter().zip(series).collect();
+                if mode == GraphMode::Share {
+                    lines.reverse();
+                }
+                for (name, values) in lines {
+                    let color = helpers::get_character_color(&colors, preferences.palette, name);
+                    let damage_points = values
                         .iter()
                         .enumerate()
-                        .map(|(i, dmg_map)| {
-                            [(i + 1) as f64, *dmg_map.get(name).unwrap() as f64]
-                        })
+                        .map(|(i, damage)| [(i + 1) as f64, *damage])
                         .collect::<Vec<[f64; 2]>>();
-                    
+
                     if !damage_points.is_empty() {
-                        plot_ui.line(
-                            Line::new(PlotPoints::from(damage_points.clone()))
-                                .name(name)
-                                .color(color)
-                                .width(2.0),
-                        );
+                        plot_ui.line(helpers::graph_line(damage_points, name, color, mode));
                     }
                 }
-                let is_there_update = self.is_there_update.blocking_lock().clone();
+                let is_there_update = *self.is_there_update.blocking_lock();
                 if is_there_update {
                     plot_ui.set_auto_bounds([true, true]);
                 }
+
+                // Clicking near a turn opens its hits
+                if plot_ui.response().clicked() {
+                    if let Some(pointer) = plot_ui.pointer_coordinate() {
+                        let turn = pointer.x.round();
+                        if turn >= 1.0 && turn <= data_buffer.turn_damage.len() as f64 {
+                            self.state.selected_turn = Some(turn as usize);
+                        }
+                    }
+                }
         });
     }
 }
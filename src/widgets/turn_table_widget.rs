@@ -0,0 +1,149 @@
+use eframe::egui::{self, Align, Id, Layout, Sense, Ui};
+use egui_extras::{Column, TableBuilder};
+use veritas_core::i18n::tr;
+use veritas_core::tr;
+
+use crate::app::DamageAnalyzer;
+
+const TURN_COLUMNS: [&str; 5] = ["Turn", "Owner", "AV", "Wave", "Cycle"];
+
+fn numeric_cell(ui: &mut Ui, text: String) {
+    ui.with_layout(Layout::right_to_left(Align::Center), |ui| ui.label(text));
+}
+
+impl DamageAnalyzer {
+    /// One row per completed turn, clicking a row opens its hits
+    pub fn show_turn_table_widget(&mut self, ui: &mut Ui) {
+        let data_buffer = self.data_buffer.blocking_lock().clone();
+        let number_format = self.state.config.read(|config| config.preferences.number_format);
+        let names = &data_buffer.column_names;
+        ui.style_mut().interaction.selectable_labels = false;
+
+        egui::ScrollArea::horizontal().id_salt("turn_table_scroll").show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt("turn_table")
+                .striped(true)
+                .resizable(true)
+                .stick_to_bottom(true)
+                .sense(Sense::click())
+                .columns(Column::auto().at_least(40.0), TURN_COLUMNS.len())
+                .columns(Column::auto().at_least(60.0), names.len() + 1)
+                .header(20.0, |mut header| {
+                    for label in TURN_COLUMNS {
+                        header.col(|ui| {
+                            ui.strong(tr(label));
+                        });
+                    }
+                    for name in names {
+                        header.col(|ui| {
+                            ui.strong(name);
+                        });
+                    }
+                    header.col(|ui| {
+                        ui.strong(tr("Total"));
+                    });
+                })
+                .body(|body| {
+                    body.rows(18.0, data_buffer.turn_damage.len(), |mut row| {
+                        let index = row.index();
+                        let turn = index + 1;
+                        let damage = &data_buffer.turn_damage[index];
+                        let record = data_buffer.turn_records.get(index).cloned().unwrap_or_default();
+                        row.set_selected(self.state.selected_turn == Some(turn));
+
+                        row.col(|ui| numeric_cell(ui, turn.to_string()));
+                        row.col(|ui| {
+                            ui.label(record.owner.unwrap_or_default());
+                        });
+                        row.col(|ui| numeric_cell(ui, format!("{:.2}", record.action_value)));
+                        row.col(|ui| numeric_cell(ui, record.wave.to_string()));
+                        row.col(|ui| numeric_cell(ui, record.cycle.to_string()));
+                        for name in names {
+                            let value = damage.get(name).copied().unwrap_or(0.0);
+                            row.col(|ui| numeric_cell(ui, number_format.format(value)));
+                        }
+                        row.col(|ui| numeric_cell(ui, number_format.format(damage.values().sum())));
+
+                        if row.response().clicked() {
+                            self.state.selected_turn = Some(turn);
+                        }
+                    });
+                });
+        });
+    }
+
+    /// Lists every hit of the selected turn
+    pub fn show_turn_detail_window(&mut self, ctx: &egui::Context) {
+        let Some(turn) = self.state.selected_turn else {
+            return;
+        };
+        let data_buffer = self.data_buffer.blocking_lock().clone();
+        let number_format = self.state.config.read(|config| config.preferences.number_format);
+        let turns = data_buffer.turn_damage.len();
+        let record = data_buffer.turn_records.get(turn - 1).cloned().unwrap_or_default();
+        let hits: Vec<_> = data_buffer.hits.iter().filter(|hit| hit.turn == turn).collect();
+        let total: f64 = hits.iter().map(|hit| hit.damage).sum();
+
+        let mut open = true;
+        egui::Window::new(tr!("Turn {}", turn))
+            .id(Id::new("turn_detail"))
+            .default_width(360.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(turn > 1, egui::Button::new(tr("Previous"))).clicked() {
+                        self.state.selected_turn = Some(turn - 1);
+                    }
+                    if ui.add_enabled(turn < turns, egui::Button::new(tr("Next"))).clicked() {
+                        self.state.selected_turn = Some(turn + 1);
+                    }
+                });
+                ui.label(tr!(
+                    "{} · AV {} · wave {} · cycle {}",
+                    record.owner.as_deref().unwrap_or("-"),
+                    format!("{:.2}", record.action_value),
+                    record.wave,
+                    record.cycle
+                ));
+                ui.separator();
+
+                if hits.is_empty() {
+                    ui.weak(tr("No hits were recorded for this turn"));
+                    return;
+                }
+                TableBuilder::new(ui)
+                    .id_salt("turn_hits")
+                    .striped(true)
+                    .column(Column::auto().at_least(24.0))
+                    .column(Column::auto().at_least(80.0))
+                    .column(Column::remainder().at_least(100.0))
+                    .columns(Column::auto().at_least(60.0), 2)
+                    .header(20.0, |mut header| {
+                        for label in ["#", "Character", "Skill", "Damage", "Share"] {
+                            header.col(|ui| {
+                                ui.strong(tr(label));
+                            });
+                        }
+                    })
+                    .body(|mut body| {
+                        for (i, hit) in hits.iter().enumerate() {
+                            body.row(18.0, |mut row| {
+                                row.col(|ui| numeric_cell(ui, (i + 1).to_string()));
+                                row.col(|ui| {
+                                    ui.label(&hit.attacker);
+                                });
+                                row.col(|ui| {
+                                    ui.label(hit.skill.as_deref().unwrap_or("-"));
+                                });
+                                row.col(|ui| numeric_cell(ui, number_format.format(hit.damage)));
+                                let share = if total > 0.0 { hit.damage / total * 100.0 } else { 0.0 };
+                                row.col(|ui| numeric_cell(ui, format!("{:.1}%", share)));
+                            });
+                        }
+                    });
+            });
+        if !open {
+            self.state.selected_turn = None;
+        }
+    }
+}
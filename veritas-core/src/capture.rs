@@ -0,0 +1,69 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::models::Packet;
+
+/// One line of a `.jsonl` capture: a raw packet and when it arrived,
+/// relative to the start of the recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedPacket {
+    pub elapsed_ms: u64,
+    #[serde(flatten)]
+    pub packet: Packet,
+}
+
+pub struct CaptureWriter {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    started: Instant,
+}
+
+impl CaptureWriter {
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        Ok(Self {
+            writer: BufWriter::new(File::create(&path)?),
+            path,
+            started: Instant::now(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write(&mut self, packet: &Packet) -> io::Result<()> {
+        let captured = CapturedPacket {
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            packet: packet.clone(),
+        };
+        serde_json::to_writer(&mut self.writer, &captured)?;
+        self.writer.write_all(b"\n")?;
+        // Flush per packet so a crash or Ctrl+C never loses a battle
+        self.writer.flush()
+    }
+}
+
+pub fn read_capture(path: impl AsRef<Path>) -> anyhow::Result<Vec<CapturedPacket>> {
+    let path = path.as_ref();
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+
+    let mut packets = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let packet = serde_json::from_str(&line)
+            .with_context(|| format!("{}:{}: invalid packet", path.display(), i + 1))?;
+        packets.push(packet);
+    }
+    Ok(packets)
+}
@@ -0,0 +1,379 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// How damage numbers are written in the UI, overlays and exported reports
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberFormat {
+    /// `12.3K`, `4.5M`, `1.2B`
+    #[default]
+    Compact,
+    /// `12,345,678`
+    Full,
+    /// `1.23e7`
+    Scientific,
+    /// `1234.57万`, `1.23亿`
+    Chinese,
+}
+
+impl NumberFormat {
+    pub const ALL: [NumberFormat; 4] = [
+        NumberFormat::Compact,
+        NumberFormat::Full,
+        NumberFormat::Scientific,
+        NumberFormat::Chinese,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NumberFormat::Compact => "Compact (12.3K, 4.5M)",
+            NumberFormat::Full => "Full (12,345,678)",
+            NumberFormat::Scientific => "Scientific (1.23e7)",
+            NumberFormat::Chinese => "万/亿 (1234.57万)",
+        }
+    }
+
+    /// Name used on the command line and in overlay URLs
+    pub fn key(self) -> &'static str {
+        match self {
+            NumberFormat::Compact => "compact",
+            NumberFormat::Full => "full",
+            NumberFormat::Scientific => "scientific",
+            NumberFormat::Chinese => "cn",
+        }
+    }
+
+    pub fn format(self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        match self {
+            NumberFormat::Compact => format_units(value, &[(1e9, "B"), (1e6, "M"), (1e3, "K")], 1),
+            NumberFormat::Full => format_thousands(value),
+            NumberFormat::Scientific if value.abs().round() < 1e3 => format_scaled(value, "", 0),
+            NumberFormat::Scientific => format!("{:.2e}", value),
+            NumberFormat::Chinese => format_units(value, &[(1e8, "亿"), (1e4, "万")], 2),
+        }
+    }
+}
+
+impl FromStr for NumberFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NumberFormat::ALL
+            .into_iter()
+            .find(|format| format.key().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown number format '{}', expected compact, full, scientific or cn", s))
+    }
+}
+
+/// Moves up to the next unit whenever the value, rounded as the current one would
+/// show it, reaches it: 999.6 becomes `1K` rather than `1000` and 999,960 `1M`
+/// rather than `1000.0K`. Trailing zeros are dropped from the decimals.
+/// `formatUnits` in the overlay follows the same rule.
+fn format_units(value: f64, units: &[(f64, &str)], decimals: usize) -> String {
+    let magnitude = value.abs();
+    let (mut size, mut suffix, mut places) = (1.0, "", 0);
+    for &(unit_size, unit_suffix) in units.iter().rev() {
+        if round_to(magnitude / size, places) * size < unit_size {
+            break;
+        }
+        (size, suffix, places) = (unit_size, unit_suffix, decimals);
+    }
+    format_scaled(value / size, suffix, places)
+}
+
+fn round_to(value: f64, decimals: usize) -> f64 {
+    let rounding = 10f64.powi(decimals as i32);
+    (value * rounding).round() / rounding
+}
+
+fn format_scaled(value: f64, suffix: &str, decimals: usize) -> String {
+    // Keeps a value that rounds to zero from showing as `-0`
+    let value = if round_to(value, decimals) == 0.0 { 0.0 } else { value };
+    let number = format!("{:.*}", decimals, value);
+    let number = if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &number
+    };
+    format!("{}{}", number, suffix)
+}
+
+fn format_thousands(value: f64) -> String {
+    let digits = format!("{:.0}", value.abs());
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    if value <= -0.5 {
+        grouped.push('-');
+    }
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// The original lineup colors, shared by the desktop charts and the web overlay
+pub const CHARACTER_COLORS: &[[u8; 3]] = &[
+    [255, 99, 132],
+    [54, 162, 235],
+    [255, 206, 86],
+    [75, 192, 192],
+    [153, 102, 255],
+    [255, 159, 64],
+    [231, 233, 237],
+    [102, 255, 102],
+];
+
+const VIVID_COLORS: &[[u8; 3]] = &[
+    [230, 25, 75],
+    [60, 180, 75],
+    [0, 130, 200],
+    [245, 130, 48],
+    [145, 30, 180],
+    [70, 240, 240],
+    [240, 50, 230],
+    [210, 245, 60],
+];
+
+/// Okabe & Ito, "Color Universal Design"
+const OKABE_ITO_COLORS: &[[u8; 3]] = &[
+    [230, 159, 0],
+    [86, 180, 233],
+    [0, 158, 115],
+    [240, 228, 66],
+    [0, 114, 178],
+    [213, 94, 0],
+    [204, 121, 167],
+    [153, 153, 153],
+];
+
+/// Paul Tol's "bright" qualitative scheme
+const TOL_BRIGHT_COLORS: &[[u8; 3]] = &[
+    [68, 119, 170],
+    [102, 204, 238],
+    [34, 136, 51],
+    [204, 187, 68],
+    [238, 102, 119],
+    [170, 51, 119],
+    [187, 187, 187],
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Palette {
+    #[default]
+    Classic,
+    Vivid,
+    OkabeIto,
+    TolBright,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 4] = [Palette::Classic, Palette::Vivid, Palette::OkabeIto, Palette::TolBright];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Palette::Classic => "Classic",
+            Palette::Vivid => "Vivid",
+            Palette::OkabeIto => "Okabe-Ito (colorblind-safe)",
+            Palette::TolBright => "Tol Bright (colorblind-safe)",
+        }
+    }
+
+    pub fn colors(self) -> &'static [[u8; 3]] {
+        match self {
+            Palette::Classic => CHARACTER_COLORS,
+            Palette::Vivid => VIVID_COLORS,
+            Palette::OkabeIto => OKABE_ITO_COLORS,
+            Palette::TolBright => TOL_BRIGHT_COLORS,
+        }
+    }
+
+    pub fn color(self, index: usize) -> [u8; 3] {
+        let colors = self.colors();
+        colors[index % colors.len()]
+    }
+}
+
+/// A character's entry in [`CharacterColors`]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharacterColor {
+    /// Index into whichever [`Palette`] is selected, so switching palettes recolors it
+    Slot(usize),
+    /// Picked by the user, kept across palettes
+    Custom([u8; 3]),
+}
+
+/// Colors keyed by character name, so a character looks the same in every
+/// battle regardless of where it sits in the lineup
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct CharacterColors(pub BTreeMap<String, CharacterColor>);
+
+impl CharacterColors {
+    /// Gives every character in `lineup` without an entry the palette slot
+    /// least used so far, skipping slots already taken by the rest of the lineup.
+    /// Returns whether anything was added.
+    pub fn assign(&mut self, lineup: &[String]) -> bool {
+        let slot_count = Palette::ALL
+            .iter()
+            .map(|palette| palette.colors().len())
+            .min()
+            .unwrap_or(1);
+        let mut added = false;
+        for name in lineup {
+            if self.0.contains_key(name) {
+                continue;
+            }
+            let taken: Vec<usize> = lineup
+                .iter()
+                .filter_map(|other| match self.0.get(other) {
+                    Some(CharacterColor::Slot(slot)) => Some(slot % slot_count),
+                    _ => None,
+                })
+                .collect();
+            let mut uses = vec![0usize; slot_count];
+            for color in self.0.values() {
+                if let CharacterColor::Slot(slot) = color {
+                    uses[slot % slot_count] += 1;
+                }
+            }
+            let slot = (0..slot_count)
+                .filter(|slot| !taken.contains(slot))
+                .min_by_key(|&slot| uses[slot])
+                .unwrap_or_else(|| fallback_slot(name, slot_count));
+            self.0.insert(name.clone(), CharacterColor::Slot(slot));
+            added = true;
+        }
+        added
+    }
+
+    /// Characters without an entry, such as those only seen in an old CSV,
+    /// still get a color that is stable across runs
+    pub fn rgb(&self, palette: Palette, name: &str) -> [u8; 3] {
+        match self.0.get(name) {
+            Some(CharacterColor::Custom(rgb)) => *rgb,
+            Some(CharacterColor::Slot(slot)) => palette.color(*slot),
+            None => palette.color(fallback_slot(name, palette.colors().len())),
+        }
+    }
+
+    pub fn hex(&self, palette: Palette, name: &str) -> String {
+        hex(self.rgb(palette, name))
+    }
+}
+
+/// FNV-1a, since `DefaultHasher` output may change between Rust releases
+fn fallback_slot(name: &str, slot_count: usize) -> usize {
+    let hash = name
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+    (hash % slot_count.max(1) as u64) as usize
+}
+
+pub fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumberFormat::{self, *};
+    use super::{fallback_slot, CharacterColor, CharacterColors, Palette};
+
+    fn formats(value: f64) -> [String; 4] {
+        NumberFormat::ALL.map(|format| format.format(value))
+    }
+
+    #[test]
+    fn rounding_carries_into_the_next_unit() {
+        assert_eq!(formats(999_960.0), ["1M", "999,960", "1.00e6", "100万"]);
+        assert_eq!(formats(999.6), ["1K", "1,000", "1.00e3", "1000"]);
+        assert_eq!(formats(999.4), ["999", "999", "999", "999"]);
+        assert_eq!(Compact.format(999_940.0), "999.9K");
+        assert_eq!(Chinese.format(99_999_999.0), "1亿");
+    }
+
+    #[test]
+    fn keeps_the_sign() {
+        assert_eq!(formats(-1_500.0), ["-1.5K", "-1,500", "-1.50e3", "-1500"]);
+        assert_eq!(formats(-999_960.0), ["-1M", "-999,960", "-1.00e6", "-100万"]);
+    }
+
+    #[test]
+    fn rounds_small_values_to_zero_without_a_sign() {
+        assert_eq!(formats(0.4), ["0", "0", "0", "0"]);
+        assert_eq!(formats(-0.4), ["0", "0", "0", "0"]);
+    }
+
+    #[test]
+    fn drops_trailing_zeros() {
+        assert_eq!(formats(1e9), ["1B", "1,000,000,000", "1.00e9", "10亿"]);
+        assert_eq!(formats(12_345_678.0), ["12.3M", "12,345,678", "1.23e7", "1234.57万"]);
+        assert_eq!(Chinese.format(12_300_000.0), "1230万");
+    }
+
+    #[test]
+    fn passes_through_non_finite_values() {
+        assert_eq!(formats(f64::NAN), ["NaN", "NaN", "NaN", "NaN"]);
+        assert_eq!(Compact.format(f64::INFINITY), "inf");
+    }
+
+    fn slot_count() -> usize {
+        Palette::ALL.iter().map(|palette| palette.colors().len()).min().unwrap()
+    }
+
+    fn lineup(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn assign_picks_the_least_used_slot() {
+        let mut colors = CharacterColors::default();
+        for slot in (0..slot_count()).filter(|&slot| slot != 3) {
+            colors.0.insert(format!("old{slot}"), CharacterColor::Slot(slot));
+        }
+        assert!(colors.assign(&lineup(&["Seele"])));
+        assert_eq!(colors.0["Seele"], CharacterColor::Slot(3));
+    }
+
+    #[test]
+    fn assign_skips_slots_the_rest_of_the_lineup_holds() {
+        let mut colors = CharacterColors::default();
+        for slot in 1..slot_count() {
+            colors.0.insert(format!("a{slot}"), CharacterColor::Slot(slot));
+            colors.0.insert(format!("b{slot}"), CharacterColor::Slot(slot));
+        }
+        colors.0.insert("Bronya".to_string(), CharacterColor::Slot(0));
+        assert!(colors.assign(&lineup(&["Bronya", "Seele"])));
+        assert_eq!(colors.0["Bronya"], CharacterColor::Slot(0));
+        assert_eq!(colors.0["Seele"], CharacterColor::Slot(1));
+    }
+
+    #[test]
+    fn assign_falls_back_once_every_slot_is_taken() {
+        let names: Vec<String> = (0..=slot_count()).map(|i| format!("c{i}")).collect();
+        let mut colors = CharacterColors::default();
+        assert!(colors.assign(&names));
+        for (slot, name) in names.iter().take(slot_count()).enumerate() {
+            assert_eq!(colors.0[name], CharacterColor::Slot(slot));
+        }
+        let last = names.last().unwrap();
+        assert_eq!(colors.0[last], CharacterColor::Slot(fallback_slot(last, slot_count())));
+    }
+
+    #[test]
+    fn assign_leaves_custom_colors_alone() {
+        let mut colors = CharacterColors::default();
+        colors.0.insert("Seele".to_string(), CharacterColor::Custom([1, 2, 3]));
+        assert!(!colors.assign(&lineup(&["Seele"])));
+
+        // A custom color does not hold a slot either
+        assert!(colors.assign(&lineup(&["Seele", "Bronya"])));
+        assert_eq!(colors.0["Seele"], CharacterColor::Custom([1, 2, 3]));
+        assert_eq!(colors.0["Bronya"], CharacterColor::Slot(0));
+    }
+}
@@ -0,0 +1,182 @@
+//! String catalog for the UI and exported reports.
+//!
+//! Strings are looked up by their English text, so untranslated ones fall back
+//! to English and call sites stay readable. Use [`tr`] for plain strings and the
+//! [`tr!`](crate::tr) macro for ones with `{}` placeholders.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+mod zh_cn;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    SimplifiedChinese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::SimplifiedChinese];
+
+    /// Always in the language itself, so it can be found from any other
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::SimplifiedChinese => "简体中文",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::SimplifiedChinese => "zh-CN",
+        }
+    }
+
+    fn catalog(self) -> Option<&'static HashMap<&'static str, &'static str>> {
+        static ZH_CN: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+        match self {
+            Language::English => None,
+            Language::SimplifiedChinese => Some(ZH_CN.get_or_init(|| zh_cn::STRINGS.iter().copied().collect())),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::ALL
+            .into_iter()
+            .find(|language| language.key().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown language '{}', expected en or zh-CN", s))
+    }
+}
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::SimplifiedChinese,
+        _ => Language::English,
+    }
+}
+
+/// `text` in the current language, or `text` itself if it has no translation
+pub fn tr(text: &'static str) -> &'static str {
+    language()
+        .catalog()
+        .and_then(|catalog| catalog.get(text).copied())
+        .unwrap_or(text)
+}
+
+/// Fills each `{}` in `template` with the next of `args`
+pub fn format(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+/// Translates a string literal, filling `{}` placeholders like `format!`:
+/// `tr!("Exported log to {}", path.display())`
+#[macro_export]
+macro_rules! tr {
+    ($text:literal) => {
+        $crate::i18n::tr($text)
+    };
+    ($text:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format($crate::i18n::tr($text), &[$(&$arg as &dyn ::std::fmt::Display),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+
+    /// The string literals passed straight to `tr(` or `tr!(` in `source`
+    fn tr_literals(source: &str) -> Vec<String> {
+        let mut literals = Vec::new();
+        for (start, call) in source.match_indices("tr") {
+            let before = source[..start].chars().next_back();
+            if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                continue;
+            }
+            let rest = &source[start + call.len()..];
+            let Some(rest) = rest.strip_prefix("!(").or_else(|| rest.strip_prefix('(')) else {
+                continue;
+            };
+            let Some(rest) = rest.trim_start().strip_prefix('"') else {
+                continue;
+            };
+
+            let mut literal = String::new();
+            let mut chars = rest.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some('n') => literal.push('\n'),
+                        Some('t') => literal.push('\t'),
+                        Some(escaped) => literal.push(escaped),
+                        None => break,
+                    },
+                    c => literal.push(c),
+                }
+            }
+            literals.push(literal);
+        }
+        literals
+    }
+
+    fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+        for entry in fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                rust_files(&path, files);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    #[test]
+    fn every_literal_is_translated() {
+        let core = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut files = Vec::new();
+        rust_files(&core.join("src"), &mut files);
+        rust_files(&core.join("../src"), &mut files);
+
+        let catalog = Language::SimplifiedChinese.catalog().unwrap();
+        let mut missing: Vec<String> = files
+            .iter()
+            .flat_map(|path| tr_literals(&fs::read_to_string(path).unwrap()))
+            .filter(|literal| !catalog.contains_key(literal.as_str()))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        assert!(missing.is_empty(), "missing from zh_cn.rs: {:#?}", missing);
+    }
+}
@@ -0,0 +1,227 @@
+//! Simplified Chinese, keyed by the English text
+
+pub(super) const STRINGS: &[(&str, &str)] = &[
+    // Menus
+    ("File", "文件"),
+    ("Launch Game...", "启动游戏..."),
+    ("Connection Settings...", "连接设置..."),
+    ("Preferences...", "偏好设置..."),
+    ("Updates...", "更新..."),
+    ("Open Log Folder", "打开日志文件夹"),
+    ("About...", "关于..."),
+    ("Tools", "工具"),
+    ("View", "视图"),
+    ("Layouts", "布局"),
+    ("Default", "默认"),
+    ("Save Layout As...", "布局另存为..."),
+    ("Delete Layout", "删除布局"),
+    ("Spawn Server", "注入服务器"),
+    ("Live Stats Server", "实时数据服务器"),
+    ("Open OBS Overlay", "打开 OBS 叠加层"),
+    ("Launching and injecting is only available on Windows", "启动和注入仅支持 Windows"),
+    // Updates
+    ("Updates", "更新"),
+    ("Veritas App", "Veritas App"),
+    ("Veritas", "Veritas"),
+    ("Current Version: {}", "当前版本：{}"),
+    ("Available Version: {}", "可用版本：{}"),
+    ("Check for Updates", "检查更新"),
+    ("Checking for updates...", "正在检查更新..."),
+    ("Veritas App v{} is available!", "Veritas App v{} 已发布！"),
+    ("Veritas App version {} is available!", "Veritas App 版本 {} 已发布！"),
+    ("Veritas App is up to date", "Veritas App 已是最新版本"),
+    ("Download Update", "下载更新"),
+    ("Update downloaded. Click 'Restart to Apply' to apply.", "更新已下载，点击“重启以应用”完成更新。"),
+    ("Restart to Apply", "重启以应用"),
+    ("Update failed: {}", "更新失败：{}"),
+    ("Failed to restart: {}", "重启失败：{}"),
+    ("Could not find update file", "找不到更新文件"),
+    ("Version Type:", "版本类型："),
+    ("Global Beta", "国际服测试版"),
+    ("CN Beta", "国服测试版"),
+    ("Global Prod", "国际服正式版"),
+    ("Not installed", "未安装"),
+    ("Veritas version {} is available!", "Veritas 版本 {} 已发布！"),
+    ("Veritas is up to date", "Veritas 已是最新版本"),
+    ("Veritas version {} has been downloaded!", "Veritas 版本 {} 已下载！"),
+    ("Failed to download update: {}", "下载更新失败：{}"),
+    ("Updated Veritas to version {}!", "Veritas 已更新到版本 {}！"),
+    ("Close", "关闭"),
+    // Layouts
+    ("Save Layout", "保存布局"),
+    ("Name:", "名称："),
+    ("Save", "保存"),
+    ("Replaces the saved layout with this name", "将替换同名的已保存布局"),
+    ("Open in New Window", "在新窗口中打开"),
+    ("Every pane is closed, reopen them from the View menu", "所有面板均已关闭，可从“视图”菜单重新打开"),
+    // Connection and launcher
+    ("Connection Settings", "连接设置"),
+    ("Server:", "服务器："),
+    ("Port:", "端口："),
+    ("Live Stats Port:", "实时数据端口："),
+    ("Restart", "重启"),
+    ("Cancel", "取消"),
+    ("Launch Game", "启动游戏"),
+    ("No game selected", "未选择游戏"),
+    ("Game Path:", "游戏路径："),
+    ("Executable", "可执行文件"),
+    ("Browse", "浏览"),
+    ("Launch", "启动"),
+    ("Failed to start live stats server on port {}: {}", "无法在端口 {} 上启动实时数据服务器：{}"),
+    // Config recovery
+    ("Settings Could Not Be Loaded", "无法加载设置"),
+    ("{} could not be read:", "无法读取 {}："),
+    ("A copy was saved to {}", "已将副本保存到 {}"),
+    (
+        "Default settings are in use and nothing will be saved until you choose:",
+        "当前使用默认设置，在你做出选择前不会保存任何更改：",
+    ),
+    ("Repair", "修复"),
+    ("Keep every setting that can still be read", "保留所有仍可读取的设置"),
+    ("The file is not valid JSON", "该文件不是有效的 JSON"),
+    ("Settings repaired", "设置已修复"),
+    ("Settings repaired, reset: {}", "设置已修复，已重置：{}"),
+    ("Settings could not be repaired", "无法修复设置"),
+    ("Reset to Defaults", "重置为默认值"),
+    ("Open Folder", "打开文件夹"),
+    // About
+    ("About Veritas", "关于 Veritas"),
+    ("Veritas App Version", "Veritas App 版本"),
+    ("Version {}", "版本 {}"),
+    ("New version available: {}", "有新版本可用：{}"),
+    ("Veritas Version", "Veritas 版本"),
+    ("Current: {}", "当前：{}"),
+    ("Source", "源代码"),
+    ("Developers", "开发者"),
+    // Preferences
+    ("Preferences", "偏好设置"),
+    ("General", "常规"),
+    ("Charts", "图表"),
+    ("Data & Logs", "数据与日志"),
+    ("Restore Defaults", "恢复默认"),
+    ("Language:", "语言："),
+    ("Theme:", "主题："),
+    ("Compact mode opacity:", "紧凑模式不透明度："),
+    ("System", "跟随系统"),
+    ("Light", "浅色"),
+    ("Dark", "深色"),
+    ("On startup:", "启动时："),
+    ("Restore layout, pin and graph options from last session", "恢复上次的布局、置顶和图表选项"),
+    ("Check for updates", "检查更新"),
+    ("Default graph unit:", "默认图表单位："),
+    ("Number format:", "数字格式："),
+    ("Compact (12.3K, 4.5M)", "紧凑 (12.3K, 4.5M)"),
+    ("Full (12,345,678)", "完整 (12,345,678)"),
+    ("Scientific (1.23e7)", "科学计数 (1.23e7)"),
+    ("Palette:", "配色："),
+    ("Classic", "经典"),
+    ("Vivid", "鲜艳"),
+    ("Okabe-Ito (colorblind-safe)", "Okabe-Ito（色盲友好）"),
+    ("Tol Bright (colorblind-safe)", "Tol Bright（色盲友好）"),
+    ("Damage over time height:", "伤害趋势图高度："),
+    ("Distribution height:", "伤害分布图高度："),
+    ("Total damage height:", "总伤害图高度："),
+    ("DpAV height:", "DpAV 图高度："),
+    ("Timeline height:", "时间轴高度："),
+    ("Histogram height:", "直方图高度："),
+    ("Character colors:", "角色颜色："),
+    ("Characters get a color the first time they appear in a battle", "角色首次在战斗中出现时会分配颜色"),
+    ("Reset", "重置"),
+    ("Go back to a color from the palette", "改回配色中的颜色"),
+    ("Forget", "移除"),
+    ("Remove until the character is seen again", "移除，直到再次遇到该角色"),
+    ("Log verbosity:", "日志详细程度："),
+    ("Damage Logs:", "伤害日志："),
+    ("Use Default", "使用默认"),
+    ("Open", "打开"),
+    ("Portable mode: settings and data are kept in {}", "便携模式：设置和数据保存在 {}"),
+    ("Place a {} file beside the executable for portable mode", "在程序旁放置 {} 文件即可启用便携模式"),
+    // Status bar
+    ("Connected", "已连接"),
+    ("Connecting...", "正在连接..."),
+    ("Pin Window", "置顶窗口"),
+    ("Unpin Window", "取消置顶"),
+    ("Compact Mode", "紧凑模式"),
+    ("Leave Compact Mode", "退出紧凑模式"),
+    // Charts and panels
+    ("Damage by Turn", "按回合伤害"),
+    ("Damage by Action Value", "按行动值伤害"),
+    ("Turn Breakdown", "回合明细"),
+    ("Damage Heatmap", "伤害热力图"),
+    ("Turn {}", "第 {} 回合"),
+    ("Previous", "上一个"),
+    ("Next", "下一个"),
+    ("{} · AV {} · wave {} · cycle {}", "{} · 行动值 {} · 第 {} 波 · 第 {} 轮"),
+    ("No hits were recorded for this turn", "该回合没有记录到伤害"),
+    ("Wave", "波次"),
+    ("Turn", "回合"),
+    ("Action Value", "行动值"),
+    ("Damage", "伤害"),
+    ("Cumulative", "累计"),
+    ("Share over Time", "占比趋势"),
+    ("Damage Distribution", "伤害分布"),
+    ("Hit Size Histogram", "单次伤害直方图"),
+    ("All Characters", "全部角色"),
+    ("Hit Damage", "单次伤害"),
+    ("Total Damage by Character", "角色总伤害"),
+    ("Character Statistics", "角色统计"),
+    ("Mean Hit", "平均单次伤害"),
+    ("Median Hit", "单次伤害中位数"),
+    ("Max Hit", "最高单次伤害"),
+    ("Actions", "行动次数"),
+    ("Damage per Action", "每次行动伤害"),
+    ("Kills", "击杀"),
+    ("Copy as TSV", "复制为 TSV"),
+    ("Copied statistics to the clipboard", "统计已复制到剪贴板"),
+    ("{}: {}% ({} dmg)", "{}：{}%（{} 伤害）"),
+    ("Action Value Metrics", "行动值统计"),
+    ("Current Turn", "当前回合"),
+    ("AV:", "行动值："),
+    ("Total Damage:", "总伤害："),
+    ("Total DpAV:", "总 DpAV："),
+    ("DpAV over Time", "DpAV 趋势"),
+    ("DpAV", "DpAV"),
+    ("Action Timeline", "行动时间轴"),
+    ("Cycle {}", "第 {} 轮"),
+    ("Wave {}", "第 {} 波"),
+    // Logs
+    ("Logs", "日志"),
+    ("Log", "日志"),
+    ("Autoscroll", "自动滚动"),
+    ("Search", "搜索"),
+    ("Clear", "清空"),
+    ("Export...", "导出..."),
+    ("Exported log to {}", "日志已导出到 {}"),
+    ("Failed to export log: {}", "导出日志失败：{}"),
+    ("DEBUG", "调试"),
+    ("INFO", "信息"),
+    ("WARN", "警告"),
+    ("ERROR", "错误"),
+    ("connection", "连接"),
+    ("battle", "战斗"),
+    ("damage", "伤害"),
+    ("update", "更新"),
+    ("app", "应用"),
+    // Reports
+    ("Character", "角色"),
+    ("Share", "占比"),
+    ("Hits", "命中数"),
+    ("Total", "总计"),
+    ("Turns", "回合数"),
+    ("Final AV", "最终行动值"),
+    ("Top hits", "最高单次伤害"),
+    ("turn", "回合"),
+    ("Skill", "技能"),
+    ("Owner", "行动者"),
+    ("AV", "行动值"),
+    ("Cycle", "轮次"),
+    ("{} (battle {})", "{}（第 {} 场战斗）"),
+    ("Battle in progress when recording stopped:", "停止录制时战斗仍在进行："),
+    ("Capture ends mid-battle:", "录制在战斗中途结束："),
+    ("Battle started: {}", "战斗开始：{}"),
+    ("Wrote {} and {}", "已写入 {} 和 {}"),
+    ("Recording to {}, press Ctrl+C to stop", "正在录制到 {}，按 Ctrl+C 停止"),
+    ("Connecting to {}:{}...", "正在连接 {}:{}..."),
+    ("Disconnected, retrying...", "连接已断开，正在重试..."),
+    ("Capture saved to {}", "录制已保存到 {}"),
+];
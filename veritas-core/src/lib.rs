@@ -0,0 +1,15 @@
+//! Parsing, aggregation and export of battle data sent by the veritas module.
+//!
+//! Feed [`models::Packet`]s into a [`packet_handler::PacketHandler`] to build up
+//! [`models::BattleData`], then turn it into a [`summary::BattleSummary`] or a
+//! [`snapshot::BattleSnapshot`]. [`capture`] reads and writes raw packet recordings.
+
+pub mod capture;
+pub mod helpers;
+pub mod i18n;
+pub mod message_logger;
+pub mod models;
+pub mod packet_handler;
+pub mod snapshot;
+pub mod stats;
+pub mod summary;
@@ -0,0 +1,158 @@
+use std::{collections::VecDeque, fmt};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// How many entries the logger keeps before dropping the oldest
+pub const MAX_LOG_ENTRIES: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 4] = [LogLevel::Debug, LogLevel::Info, LogLevel::Warn, LogLevel::Error];
+
+    pub fn to_log_level(self) -> log::Level {
+        match self {
+            LogLevel::Debug => log::Level::Debug,
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Error => log::Level::Error,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LogCategory {
+    Connection,
+    Battle,
+    Damage,
+    Update,
+    /// Anything about the app itself, e.g. window state or the live stats server
+    App,
+}
+
+impl LogCategory {
+    pub const ALL: [LogCategory; 5] = [
+        LogCategory::Connection,
+        LogCategory::Battle,
+        LogCategory::Damage,
+        LogCategory::Update,
+        LogCategory::App,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogCategory::Connection => "connection",
+            LogCategory::Battle => "battle",
+            LogCategory::Damage => "damage",
+            LogCategory::Update => "update",
+            LogCategory::App => "app",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: LogLevel,
+    pub category: LogCategory,
+    pub message: String,
+}
+
+impl LogEntry {
+    pub fn new(level: LogLevel, category: LogCategory, message: impl Into<String>) -> Self {
+        Self {
+            timestamp: Local::now(),
+            level,
+            category,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {:<5} {}: {}",
+            self.timestamp.format("%H:%M:%S"),
+            self.level.label(),
+            self.category.label(),
+            self.message
+        )
+    }
+}
+
+/// Ring buffer of the most recent [`LogEntry`]s
+#[derive(Clone)]
+pub struct MessageLogger {
+    entries: VecDeque<LogEntry>,
+}
+
+impl Default for MessageLogger {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::with_capacity(MAX_LOG_ENTRIES),
+        }
+    }
+}
+
+impl MessageLogger {
+    pub fn log(&mut self, level: LogLevel, category: LogCategory, message: impl Into<String>) {
+        self.push(LogEntry::new(level, category, message));
+    }
+
+    /// Also forwards the entry to the `log` facade; per-hit damage lines
+    /// are demoted to debug there so they don't crowd out everything else
+    pub fn push(&mut self, entry: LogEntry) {
+        let level = match entry.category {
+            LogCategory::Damage => log::Level::Debug,
+            _ => entry.level.to_log_level(),
+        };
+        log::log!(target: "veritas_core::log", level, "{}: {}", entry.category.label(), entry.message);
+
+        if self.entries.len() == MAX_LOG_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn get_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Avatar {
+    pub name: String,
+}
+
+impl fmt::Display for Avatar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Skill {
+    pub name: String,
+    pub r#type: String,
+}
+
+impl fmt::Display for Skill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.r#type, self.name)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SkillData {
+    pub avatar: Avatar,
+    pub skill: Skill 
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ErrorData {
+    pub msg: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DamageData {
+    pub attacker: Avatar,
+    pub damage: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TurnData {
+    pub avatars: Vec<Avatar>,
+    pub turn_info: TurnInfo
+}
+
+#[derive(Clone, Debug, Deserialize, Default)]
+pub struct TurnInfo {
+    pub action_value: f64,
+    pub cycle: u32,
+    pub wave: u32,
+    pub avatars_turn_damage: Vec<f64>,
+    pub total_damage: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TurnBeginData {
+    pub action_value: f64,
+    pub turn_owner: Option<Avatar>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Packet {
+    pub r#type: String,
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KillData {
+    pub attacker: Avatar,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetupData {
+    pub avatars: Vec<Avatar>,
+}
+
+/// A received packet after parsing, tagged with where in the battle it happened.
+#[derive(Debug, Clone, Serialize)]
+pub struct BattleEvent {
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub turn: usize,
+    pub action_value: f64,
+    pub cycle: u32,
+    pub wave: u32,
+    pub turn_owner: Option<String>,
+    #[serde(flatten)]
+    pub kind: BattleEventKind,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BattleEventKind {
+    Lineup {
+        avatars: Vec<String>,
+    },
+    BattleBegin,
+    TurnBegin,
+    UseSkill {
+        avatar: String,
+        skill_name: String,
+        skill_type: String,
+    },
+    Damage {
+        attacker: String,
+        damage: f64,
+        /// Last skill `attacker` used this turn, if any
+        skill: Option<String>,
+    },
+    TurnEnd {
+        damage: HashMap<String, f64>,
+        total_damage: f64,
+    },
+    Kill {
+        attacker: String,
+    },
+    BattleEnd {
+        total_damage: f64,
+    },
+    Error {
+        msg: String,
+    },
+    Unknown {
+        packet_type: String,
+        data: serde_json::Value,
+    },
+}
+
+/// A single damage event from a lineup character
+#[derive(Debug, Clone, Serialize)]
+pub struct Hit {
+    pub attacker: String,
+    pub damage: f64,
+    /// 1-based index into `turn_damage` once the turn has ended
+    pub turn: usize,
+    pub action_value: f64,
+    pub skill: Option<String>,
+}
+
+/// Context for the turn at the same index in `turn_damage`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TurnRecord {
+    pub action_value: f64,
+    pub owner: Option<String>,
+    pub cycle: u32,
+    pub wave: u32,
+}
+
+/// Everything known about the current battle, updated by [`crate::packet_handler::PacketHandler`]
+#[derive(Debug, Clone, Default)]
+pub struct BattleData {
+    pub rows: Vec<Vec<f64>>,
+    pub column_names: Vec<String>,
+    pub total_damage: HashMap<String, f64>,
+    pub av_damage: Vec<HashMap<String, f64>>,
+    pub turn_damage: Vec<HashMap<String, f64>>,
+    pub turn_records: Vec<TurnRecord>,
+    pub hits: Vec<Hit>,
+    pub kills: HashMap<String, u32>,
+    pub current_turn: HashMap<String, f64>,
+    pub current_av: f64,
+    pub current_cycle: u32,
+    pub current_wave: u32,
+    pub current_turn_owner: Option<String>,
+    pub av_history: Vec<f64>,
+    pub total_dpav: f64,
+    pub dpav_history: Vec<f64>,
+}
+
+impl BattleData {
+    pub fn init_characters(&mut self, names: &[String]) {
+        self.column_names = names.to_vec();
+        self.total_damage = names.iter().map(|name| (name.clone(), 0.0)).collect();
+        self.current_turn = names.iter().map(|name| (name.clone(), 0.0)).collect();
+        self.turn_damage.clear();
+        self.turn_records.clear();
+        self.hits.clear();
+        self.kills.clear();
+        self.av_history.clear();
+        self.av_damage.clear();
+        self.current_av = 0.0;
+        self.current_cycle = 0;
+        self.current_wave = 0;
+        self.current_turn_owner = None;
+        self.total_dpav = 0.0;
+        self.dpav_history.clear();
+    }
+
+    /// Closes the current turn, recording its damage alongside its context
+    pub fn push_turn(&mut self, damage: HashMap<String, f64>) {
+        self.turn_damage.push(damage);
+        self.turn_records.push(TurnRecord {
+            action_value: self.current_av,
+            owner: self.current_turn_owner.clone(),
+            cycle: self.current_cycle,
+            wave: self.current_wave,
+        });
+    }
+
+    pub fn update_dpav(&mut self, av: f64) {
+        if av > 0.0 {
+            let total_damage: f64 = self.total_damage.values().sum();
+            let dpav = total_damage / av;
+            self.dpav_history.push(dpav);
+            
+            self.total_dpav = dpav;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,379 @@
+use std::{collections::HashMap, fs::{self, File}, path::PathBuf};
+
+use csv::Writer;
+
+use crate::message_logger::{LogCategory, LogEntry, LogLevel};
+use crate::models::{BattleData, BattleEvent, BattleEventKind, DamageData, ErrorData, Hit, KillData, Packet, SetupData, SkillData, TurnData, TurnBeginData};
+
+/// What handling a single packet produced
+#[derive(Debug, Default)]
+pub struct PacketOutput {
+    /// Whether the battle data changed and views should refresh
+    pub updated: bool,
+    pub events: Vec<BattleEvent>,
+    /// Log entries, oldest first
+    pub messages: Vec<LogEntry>,
+}
+
+impl PacketOutput {
+    fn log(&mut self, level: LogLevel, category: LogCategory, message: impl Into<String>) {
+        self.messages.push(LogEntry::new(level, category, message));
+    }
+
+    /// Records an event during the turn still in progress
+    fn emit(&mut self, data_buffer: &BattleData, kind: BattleEventKind) {
+        self.emit_at(data_buffer, data_buffer.turn_damage.len() + 1, kind);
+    }
+
+    fn emit_at(&mut self, data_buffer: &BattleData, turn: usize, kind: BattleEventKind) {
+        self.events.push(BattleEvent {
+            timestamp: chrono::Local::now(),
+            turn,
+            action_value: data_buffer.current_av,
+            cycle: data_buffer.current_cycle,
+            wave: data_buffer.current_wave,
+            turn_owner: data_buffer.current_turn_owner.clone(),
+            kind,
+        });
+    }
+}
+
+/// Turns raw packets from the injected module into [`BattleData`] updates,
+/// writing a `HSR_<timestamp>.csv` per battle to its output directory along the way.
+pub struct PacketHandler {
+    csv_writer: Option<Writer<File>>,
+    csv_enabled: bool,
+    output_dir: PathBuf,
+    current_file: String,
+    // Skill each avatar last used this turn, used to attribute damage
+    last_skills: HashMap<String, String>,
+}
+
+impl Default for PacketHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PacketHandler {
+    pub fn new() -> Self {
+        Self {
+            csv_writer: None,
+            csv_enabled: true,
+            output_dir: PathBuf::from("damage_logs"),
+            current_file: String::new(),
+            last_skills: HashMap::new(),
+        }
+    }
+
+    /// Disable writing CSVs, e.g. when summarizing an existing capture
+    pub fn set_csv_enabled(&mut self, enabled: bool) {
+        self.csv_enabled = enabled;
+    }
+
+    /// Directory the next battle's CSV is written to, `damage_logs` by default
+    pub fn set_output_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.output_dir = dir.into();
+    }
+
+    /// Path of the CSV for the current (or last) battle, empty if none was written
+    pub fn current_file(&self) -> &str {
+        &self.current_file
+    }
+
+    pub fn handle_packet(&mut self, data_buffer: &mut BattleData, packet: Packet) -> PacketOutput {
+        let mut output = PacketOutput {
+            updated: true,
+            ..Default::default()
+        };
+        match packet.r#type.as_str() {
+            "OnSetBattleLineup" => self.handle_lineup(packet.data, &mut output, data_buffer),
+            // TODO: Handle packet
+            "OnBattleBegin" => self.handle_battle_begin(packet.data, &mut output, data_buffer),
+            "OnTurnBegin" => self.handle_turn_begin(packet.data, &mut output, data_buffer),
+            "OnDamage" => self.handle_damage(packet.data, &mut output, data_buffer),
+            "OnTurnEnd" => self.handle_turn_end(packet.data, &mut output, data_buffer),
+            "OnKill" => self.handle_kill(packet.data, &mut output, data_buffer),
+            "OnBattleEnd" => self.handle_battle_end(&mut output, data_buffer),
+            "OnUseSkill" => self.handle_on_skill_use(packet.data, &mut output, data_buffer),
+            "Error" => self.handle_error(packet.data, &mut output, data_buffer),
+            // TODO: OnUpdateWave, OnUpdateCycle
+            _ => {
+                output.updated = false;
+                output.log(LogLevel::Warn, LogCategory::Battle, format!("Unknown packet type: {}", packet.r#type));
+                output.emit(data_buffer, BattleEventKind::Unknown {
+                    packet_type: packet.r#type,
+                    data: packet.data,
+                });
+            },
+        }
+        output
+    }
+
+    fn handle_turn_begin(
+        &mut self,
+        data: serde_json::Value,
+        output: &mut PacketOutput,
+        data_buffer: &mut BattleData
+    ) {
+        if let Ok(turn_data) = serde_json::from_value::<TurnBeginData>(data) {
+            data_buffer.current_av = turn_data.action_value;
+            data_buffer.current_turn_owner = turn_data.turn_owner.map(|owner| owner.name);
+            self.last_skills.clear();
+            output.log(LogLevel::Info, LogCategory::Battle, format!("Turn begin - AV: {:.2}", turn_data.action_value));
+            output.emit(data_buffer, BattleEventKind::TurnBegin);
+        }
+    }
+    
+    fn handle_turn_end(
+        &mut self,
+        data: serde_json::Value,
+        output: &mut PacketOutput,
+        data_buffer: &mut BattleData
+    ) {
+        if let Ok(turn_data) = serde_json::from_value::<TurnData>(data) {
+            for (avatar, &damage) in turn_data.avatars.iter().zip(turn_data.turn_info.avatars_turn_damage.iter()) {
+                // If key doesn't exist, create
+                if !data_buffer.current_turn.contains_key(&avatar.name) {
+                    data_buffer.current_turn.insert(avatar.name.clone(), 0.0);
+                }
+                if damage > 0.0 {
+                    output.log(LogLevel::Info, LogCategory::Damage, format!(
+                        "Turn summary - {}: {} damage",
+                        avatar.name, damage
+                    ));
+                }
+            }
+            output.log(LogLevel::Info, LogCategory::Damage, format!("Total turn damage: {}", turn_data.turn_info.total_damage));
+
+            data_buffer.current_cycle = turn_data.turn_info.cycle;
+            data_buffer.current_wave = turn_data.turn_info.wave;
+
+            let current_av = data_buffer.current_av;
+            
+            let current = data_buffer.current_turn.clone();
+
+            output.emit(data_buffer, BattleEventKind::TurnEnd {
+                damage: current.clone(),
+                total_damage: turn_data.turn_info.total_damage,
+            });
+
+            let len = data_buffer.av_history.len();
+            if len > 0 {
+                // New AV
+                if data_buffer.av_history[len - 1] != current_av {
+                    data_buffer.av_history.push(current_av);
+                    data_buffer.av_damage.push(current.clone());    
+                }
+                // Same AV as before
+                else {
+                    let map = &mut data_buffer.av_damage[len - 1];
+                    let current_copy = current.clone();
+                    for (k, v) in map {
+                        let dmg = current_copy.get(k).unwrap();
+                        *v += *dmg;
+                    }
+                }
+            }
+            else {
+                data_buffer.av_history.push(current_av);
+                data_buffer.av_damage.push(current.clone());
+            }
+            
+            
+            data_buffer.push_turn(current);
+            if current_av > 0.0 {
+                data_buffer.update_dpav(current_av);
+            }
+
+            data_buffer.current_turn.clear();
+    }
+    }
+    
+    fn handle_lineup(
+        &mut self,
+        data: serde_json::Value,
+        output: &mut PacketOutput,
+        data_buffer: &mut BattleData
+    ) {
+        if let Ok(lineup_data) = serde_json::from_value::<SetupData>(data) {
+
+            let names: Vec<String> = lineup_data.avatars.iter().map(|a| a.name.clone()).collect();
+
+            if self.csv_enabled {
+                self.create_csv(&names, output);
+            }
+
+            data_buffer.init_characters(&names);
+            data_buffer.rows.clear();
+            output.log(LogLevel::Debug, LogCategory::Battle, format!("Headers: {:?}", names));
+
+            output.emit(data_buffer, BattleEventKind::Lineup { avatars: names });
+        }
+    }
+
+    fn create_csv(&mut self, names: &[String], output: &mut PacketOutput) {
+        fs::create_dir_all(&self.output_dir).unwrap_or_else(|e| {
+            output.log(LogLevel::Error, LogCategory::Battle, format!("Failed to create {} directory: {}", self.output_dir.display(), e));
+        });
+
+        let filename = format!("HSR_{}.csv", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+        let path = self.output_dir.join(&filename).to_string_lossy().into_owned();
+
+        match File::create(&path) {
+            Ok(file) => {
+                self.csv_writer = Some(Writer::from_writer(file));
+                self.current_file = path.clone();
+
+                if let Some(writer) = &mut self.csv_writer {
+                    if let Err(e) = writer.write_record(names) {
+                        output.log(LogLevel::Error, LogCategory::Battle, format!("Failed to write CSV headers: {}", e));
+                    }
+                }
+
+                output.log(LogLevel::Info, LogCategory::Battle, format!("Created CSV: {}", filename));
+            }
+            Err(e) => {
+                output.log(LogLevel::Error, LogCategory::Battle, format!("Failed to create CSV file: {}", e));
+            }
+        }
+    }
+    
+    fn handle_battle_begin(&mut self,
+        _data: serde_json::Value,
+        output: &mut PacketOutput,
+        data_buffer: &mut BattleData
+    ) {
+        output.log(LogLevel::Info, LogCategory::Battle, "Battle started");
+        output.emit(data_buffer, BattleEventKind::BattleBegin);
+    }
+    
+    fn handle_damage(
+        &mut self,
+        data: serde_json::Value,
+        output: &mut PacketOutput,
+        data_buffer: &mut BattleData
+    ) {
+        if let Ok(damage_data) = serde_json::from_value::<DamageData>(data) {
+        let attacker = damage_data.attacker.name.clone();
+        let damage = damage_data.damage;
+        
+        if damage > 0.0 {
+            output.log(LogLevel::Info, LogCategory::Damage, format!("{} dealt {} damage", attacker, damage));
+        }
+        
+        let mut should_write = false;
+        let mut row = vec![0.0; data_buffer.column_names.len()];
+        
+        if let Some(idx) = data_buffer.column_names.iter().position(|name| name == &attacker) {
+            row[idx] = damage;
+            *data_buffer.total_damage.entry(attacker.clone()).or_insert(0.0) += damage;
+            *data_buffer.current_turn.entry(attacker.clone()).or_insert(0.0) += damage;
+            should_write = true;
+        }
+        data_buffer.rows.push(row.clone());
+    
+        let skill = self.last_skills.get(&attacker).cloned();
+
+        if should_write {
+            if let Some(writer) = &mut self.csv_writer {
+                let _ = writer.write_record(row.iter().map(|&x| x.to_string()).collect::<Vec<_>>());
+                let _ = writer.flush();
+            }
+
+            let hit = Hit {
+                attacker: attacker.clone(),
+                damage,
+                turn: data_buffer.turn_damage.len() + 1,
+                action_value: data_buffer.current_av,
+                skill: skill.clone(),
+            };
+            data_buffer.hits.push(hit);
+        }
+
+        output.emit(data_buffer, BattleEventKind::Damage { attacker, damage, skill });
+    }
+    }
+    
+    fn handle_kill(
+        &mut self,
+        data: serde_json::Value,
+        output: &mut PacketOutput,
+        data_buffer: &mut BattleData
+    ) {
+        if let Ok(kill_data) = serde_json::from_value::<KillData>(data) {
+            output.log(LogLevel::Info, LogCategory::Battle, format!("{} has killed", kill_data.attacker.name));
+            *data_buffer.kills.entry(kill_data.attacker.name.clone()).or_insert(0) += 1;
+            output.emit(data_buffer, BattleEventKind::Kill { attacker: kill_data.attacker.name });
+        }
+    }
+    
+    fn handle_battle_end(
+        &mut self,
+        output: &mut PacketOutput,
+        data_buffer: &mut BattleData
+    ) {
+        let final_turn_data = if !data_buffer.current_turn.is_empty() {
+            let total_damage: f64 = data_buffer.current_turn.values().sum();
+            let final_turn = data_buffer.current_turn.clone();
+            let av = data_buffer.current_av;
+
+            data_buffer.update_dpav(av);
+            data_buffer.push_turn(final_turn.clone());
+
+            Some((final_turn, total_damage))
+        } else {
+            None
+        };
+    
+        if let Some((final_turn, total_damage)) = final_turn_data {
+            for (name, damage) in final_turn {
+                if damage > 0.0 {
+                    output.log(LogLevel::Info, LogCategory::Damage, format!(
+                        "Final turn summary - {}: {} damage",
+                        name, damage
+                    ));
+                }
+            }
+            output.log(LogLevel::Info, LogCategory::Damage, format!("Final turn total damage: {}", total_damage));
+        }
+    
+        self.csv_writer = None;
+        output.log(LogLevel::Info, LogCategory::Battle, "Battle ended - CSV file closed");
+
+        // Every turn is closed by now, so the battle ends on the last one recorded
+        let total_damage = data_buffer.total_damage.values().sum();
+        let last_turn = data_buffer.turn_damage.len().max(1);
+        output.emit_at(data_buffer, last_turn, BattleEventKind::BattleEnd { total_damage });
+    }
+
+    fn handle_on_skill_use(
+        &mut self,
+        data: serde_json::Value,
+        output: &mut PacketOutput,
+        data_buffer: &mut BattleData
+    ) {
+        if let Ok(skill_data) = serde_json::from_value::<SkillData>(data) {
+            output.log(LogLevel::Info, LogCategory::Battle, format!("{} used {}", skill_data.avatar, skill_data.skill));
+            self.last_skills.insert(skill_data.avatar.name.clone(), skill_data.skill.name.clone());
+            output.emit(data_buffer, BattleEventKind::UseSkill {
+                avatar: skill_data.avatar.name,
+                skill_name: skill_data.skill.name,
+                skill_type: skill_data.skill.r#type,
+            });
+        }    
+    }
+
+
+    fn handle_error(
+        &mut self,
+        data: serde_json::Value,
+        output: &mut PacketOutput,
+        data_buffer: &mut BattleData
+    ) {
+        if let Ok(error) = serde_json::from_value::<ErrorData>(data) {
+            output.log(LogLevel::Error, LogCategory::Battle, error.msg.clone());
+            output.emit(data_buffer, BattleEventKind::Error { msg: error.msg });
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use serde::Serialize;
+
+use crate::models::BattleData;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CharacterSnapshot {
+    pub name: String,
+    pub total_damage: f64,
+    pub share: f64,
+    pub last_turn_damage: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TurnSnapshot {
+    pub turn: usize,
+    pub total_damage: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BattleSnapshot {
+    pub lineup: Vec<CharacterSnapshot>,
+    pub total_damage: f64,
+    pub current_av: f64,
+    pub current_cycle: u32,
+    pub current_wave: u32,
+    pub total_dpav: f64,
+    pub turn_count: usize,
+    pub last_turn: Option<TurnSnapshot>,
+}
+
+impl BattleSnapshot {
+    pub fn from_battle(buffer: &BattleData) -> Self {
+        let total_damage: f64 = buffer.total_damage.values().sum();
+        let last_turn = buffer.turn_damage.last();
+
+        let lineup = buffer.column_names
+            .iter()
+            .map(|name| {
+                let damage = buffer.total_damage.get(name).copied().unwrap_or(0.0);
+                CharacterSnapshot {
+                    name: name.clone(),
+                    total_damage: damage,
+                    share: if total_damage > 0.0 { damage / total_damage } else { 0.0 },
+                    last_turn_damage: last_turn
+                        .and_then(|turn| turn.get(name))
+                        .copied()
+                        .unwrap_or(0.0),
+                }
+            })
+            .collect();
+
+        Self {
+            lineup,
+            total_damage,
+            current_av: buffer.current_av,
+            current_cycle: buffer.current_cycle,
+            current_wave: buffer.current_wave,
+            total_dpav: buffer.total_dpav,
+            turn_count: buffer.turn_damage.len(),
+            last_turn: last_turn.map(|turn| TurnSnapshot {
+                turn: buffer.turn_damage.len(),
+                total_damage: turn.values().sum(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Per-character statistics for the current battle.
+
+use serde::Serialize;
+
+use crate::i18n::tr;
+use crate::models::BattleData;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CharacterStats {
+    pub name: String,
+    pub total_damage: f64,
+    pub share: f64,
+    pub hits: usize,
+    pub mean_hit: f64,
+    pub median_hit: f64,
+    pub max_hit: f64,
+    /// Completed turns the character owned
+    pub actions: usize,
+    pub damage_per_action: f64,
+    pub kills: u32,
+    pub damage_per_av: f64,
+}
+
+impl CharacterStats {
+    /// One entry per lineup character, in lineup order
+    pub fn from_battle(buffer: &BattleData) -> Vec<Self> {
+        let total_damage: f64 = buffer.total_damage.values().sum();
+        buffer.column_names
+            .iter()
+            .map(|name| {
+                let mut hits: Vec<f64> = buffer.hits
+                    .iter()
+                    .filter(|hit| hit.attacker == *name && hit.damage > 0.0)
+                    .map(|hit| hit.damage)
+                    .collect();
+                hits.sort_by(f64::total_cmp);
+                let damage = buffer.total_damage.get(name).copied().unwrap_or(0.0);
+                let actions = buffer.turn_records
+                    .iter()
+                    .filter(|record| record.owner.as_ref() == Some(name))
+                    .count();
+
+                Self {
+                    name: name.clone(),
+                    total_damage: damage,
+                    share: ratio(damage, total_damage),
+                    hits: hits.len(),
+                    mean_hit: ratio(hits.iter().sum(), hits.len() as f64),
+                    median_hit: median(&hits),
+                    max_hit: hits.last().copied().unwrap_or(0.0),
+                    actions,
+                    damage_per_action: ratio(damage, actions as f64),
+                    kills: buffer.kills.get(name).copied().unwrap_or(0),
+                    damage_per_av: ratio(damage, buffer.current_av),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Tab separated rows with a header, numbers unformatted so spreadsheets can parse them
+pub fn to_tsv(stats: &[CharacterStats]) -> String {
+    let mut out = [
+        tr("Character"),
+        tr("Damage"),
+        tr("Share"),
+        tr("Hits"),
+        tr("Mean Hit"),
+        tr("Median Hit"),
+        tr("Max Hit"),
+        tr("Actions"),
+        tr("Damage per Action"),
+        tr("Kills"),
+        tr("DpAV"),
+    ]
+    .join("\t");
+    out.push('\n');
+    for character in stats {
+        out.push_str(&format!(
+            "{}\t{:.0}\t{:.4}\t{}\t{:.0}\t{:.0}\t{:.0}\t{}\t{:.0}\t{}\t{:.2}\n",
+            character.name,
+            character.total_damage,
+            character.share,
+            character.hits,
+            character.mean_hit,
+            character.median_hit,
+            character.max_hit,
+            character.actions,
+            character.damage_per_action,
+            character.kills,
+            character.damage_per_av,
+        ));
+    }
+    out
+}
+
+fn ratio(value: f64, over: f64) -> f64 {
+    if over > 0.0 {
+        value / over
+    } else {
+        0.0
+    }
+}
+
+/// Median of already sorted values
+fn median(sorted: &[f64]) -> f64 {
+    percentile(sorted, 0.5)
+}
+
+/// The `p` (0 to 1) percentile of already sorted values, interpolating between neighbours
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = p.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let below = sorted[rank.floor() as usize];
+    let above = sorted[rank.ceil() as usize];
+    below + (above - below) * rank.fract()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::models::Hit;
+
+    fn hit(attacker: &str, damage: f64) -> Hit {
+        Hit { attacker: attacker.to_string(), damage, turn: 1, action_value: 0.0, skill: None }
+    }
+
+    /// Seele acts twice and kills once, Bronya acts once, Pela never acts
+    fn battle() -> BattleData {
+        let names = ["Seele", "Bronya", "Pela"].map(String::from);
+        let mut buffer = BattleData::default();
+        buffer.init_characters(&names);
+        buffer.hits = vec![
+            hit("Seele", 100.0),
+            hit("Seele", 300.0),
+            hit("Bronya", 50.0),
+            hit("Seele", 200.0),
+            hit("Bronya", 0.0),
+        ];
+        buffer.total_damage = HashMap::from([
+            ("Seele".to_string(), 600.0),
+            ("Bronya".to_string(), 50.0),
+            ("Pela".to_string(), 0.0),
+        ]);
+        buffer.kills.insert("Seele".to_string(), 1);
+        for owner in ["Seele", "Bronya", "Seele"] {
+            buffer.current_turn_owner = Some(owner.to_string());
+            buffer.push_turn(HashMap::new());
+        }
+        buffer.current_av = 130.0;
+        buffer
+    }
+
+    #[test]
+    fn percentile_interpolates_between_neighbours() {
+        assert_eq!(percentile(&[1.0, 2.0, 4.0], 0.5), 2.0);
+        assert_eq!(percentile(&[1.0, 2.0, 4.0, 8.0], 0.5), 3.0);
+        assert_eq!(percentile(&[1.0, 2.0, 4.0, 8.0], 0.0), 1.0);
+        assert_eq!(percentile(&[1.0, 2.0, 4.0, 8.0], 1.0), 8.0);
+        assert_eq!(percentile(&[5.0], 0.9), 5.0);
+    }
+
+    #[test]
+    fn percentile_of_nothing_is_zero() {
+        assert_eq!(percentile(&[], 0.5), 0.0);
+        assert_eq!(median(&[]), 0.0);
+    }
+
+    #[test]
+    fn from_battle_counts_hits_actions_and_kills() {
+        let stats = CharacterStats::from_battle(&battle());
+        let names: Vec<_> = stats.iter().map(|character| character.name.as_str()).collect();
+        assert_eq!(names, ["Seele", "Bronya", "Pela"]);
+
+        let seele = &stats[0];
+        assert_eq!(seele.total_damage, 600.0);
+        assert_eq!(seele.share, 600.0 / 650.0);
+        assert_eq!(seele.hits, 3);
+        assert_eq!(seele.mean_hit, 200.0);
+        assert_eq!(seele.median_hit, 200.0);
+        assert_eq!(seele.max_hit, 300.0);
+        assert_eq!(seele.actions, 2);
+        assert_eq!(seele.damage_per_action, 300.0);
+        assert_eq!(seele.kills, 1);
+        assert_eq!(seele.damage_per_av, 600.0 / 130.0);
+
+        // The zero damage hit is not counted
+        let bronya = &stats[1];
+        assert_eq!((bronya.hits, bronya.actions, bronya.kills), (1, 1, 0));
+    }
+
+    #[test]
+    fn from_battle_without_damage_is_all_zero() {
+        let stats = CharacterStats::from_battle(&battle());
+        let pela = &stats[2];
+        assert_eq!((pela.hits, pela.actions, pela.kills), (0, 0, 0));
+        assert_eq!([pela.share, pela.mean_hit, pela.median_hit, pela.max_hit, pela.damage_per_action], [0.0; 5]);
+
+        let mut empty = BattleData::default();
+        empty.init_characters(&["Seele".to_string()]);
+        let stats = CharacterStats::from_battle(&empty);
+        assert_eq!((stats[0].share, stats[0].damage_per_av), (0.0, 0.0));
+        assert!(CharacterStats::from_battle(&BattleData::default()).is_empty());
+    }
+
+    #[test]
+    fn to_tsv_writes_a_header_and_one_row_per_character() {
+        let tsv = to_tsv(&CharacterStats::from_battle(&battle()));
+        let lines: Vec<_> = tsv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].split('\t').count(), 11);
+        assert_eq!(lines[1], "Seele\t600\t0.9231\t3\t200\t200\t300\t2\t300\t1\t4.62");
+        assert_eq!(lines[3], "Pela\t0\t0.0000\t0\t0\t0\t0\t0\t0\t0\t0.00");
+        assert_eq!(to_tsv(&[]).lines().count(), 1);
+    }
+}
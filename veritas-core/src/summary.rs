@@ -0,0 +1,314 @@
+use std::{fmt, path::Path};
+
+use anyhow::Context;
+use serde::Serialize;
+
+use crate::helpers::NumberFormat;
+use crate::i18n::tr;
+use crate::models::BattleData;
+
+pub const DEFAULT_TOP_HITS: usize = 10;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CharacterSummary {
+    pub name: String,
+    pub total_damage: f64,
+    pub share: f64,
+    pub hits: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HitSummary {
+    pub attacker: String,
+    pub damage: f64,
+    pub turn: Option<usize>,
+    pub skill: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TurnSummary {
+    pub turn: usize,
+    pub owner: Option<String>,
+    pub action_value: f64,
+    /// Damage per character, in lineup order
+    pub damage: Vec<f64>,
+    pub total_damage: f64,
+}
+
+/// Totals for one battle. Turn and AV figures are only known when the
+/// summary comes from a live battle or a capture, not from a damage log CSV.
+#[derive(Debug, Clone, Serialize)]
+pub struct BattleSummary {
+    pub characters: Vec<CharacterSummary>,
+    pub total_damage: f64,
+    pub turns: Option<usize>,
+    pub final_av: Option<f64>,
+    pub total_dpav: Option<f64>,
+    pub top_hits: Vec<HitSummary>,
+    pub turn_table: Vec<TurnSummary>,
+}
+
+impl BattleSummary {
+    pub fn from_battle(buffer: &BattleData, top_hits: usize) -> Self {
+        let hits = buffer.hits
+            .iter()
+            .map(|hit| HitSummary {
+                attacker: hit.attacker.clone(),
+                damage: hit.damage,
+                turn: Some(hit.turn),
+                skill: hit.skill.clone(),
+            })
+            .collect();
+
+        let mut summary = Self::from_hits(&buffer.column_names, hits, top_hits);
+        summary.turns = Some(buffer.turn_damage.len());
+        summary.final_av = Some(buffer.current_av);
+        summary.total_dpav = Some(buffer.total_dpav);
+        summary.turn_table = buffer.turn_damage
+            .iter()
+            .zip(buffer.turn_records.iter())
+            .enumerate()
+            .map(|(i, (damage, record))| {
+                let damage: Vec<f64> = buffer.column_names
+                    .iter()
+                    .map(|name| damage.get(name).copied().unwrap_or(0.0))
+                    .collect();
+                TurnSummary {
+                    turn: i + 1,
+                    owner: record.owner.clone(),
+                    action_value: record.action_value,
+                    total_damage: damage.iter().sum(),
+                    damage,
+                }
+            })
+            .collect();
+        summary
+    }
+
+    /// Reads a `damage_logs/*.csv` file: a header of character names, then one row per hit
+    pub fn from_csv(path: impl AsRef<Path>, top_hits: usize) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let mut reader = csv::Reader::from_path(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let names: Vec<String> = reader.headers()?.iter().map(str::to_string).collect();
+
+        let mut hits = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            for (name, value) in names.iter().zip(record.iter()) {
+                let damage: f64 = value.parse().unwrap_or(0.0);
+                if damage > 0.0 {
+                    hits.push(HitSummary {
+                        attacker: name.clone(),
+                        damage,
+                        turn: None,
+                        skill: None,
+                    });
+                }
+            }
+        }
+
+        Ok(Self::from_hits(&names, hits, top_hits))
+    }
+
+    fn from_hits(names: &[String], mut hits: Vec<HitSummary>, top_hits: usize) -> Self {
+        let mut characters: Vec<CharacterSummary> = names
+            .iter()
+            .map(|name| CharacterSummary {
+                name: name.clone(),
+                total_damage: 0.0,
+                share: 0.0,
+                hits: 0,
+            })
+            .collect();
+
+        for hit in hits.iter().filter(|hit| hit.damage > 0.0) {
+            if let Some(character) = characters.iter_mut().find(|c| c.name == hit.attacker) {
+                character.total_damage += hit.damage;
+                character.hits += 1;
+            }
+        }
+
+        let total_damage: f64 = characters.iter().map(|c| c.total_damage).sum();
+        if total_damage > 0.0 {
+            for character in &mut characters {
+                character.share = character.total_damage / total_damage;
+            }
+        }
+
+        hits.sort_by(|a, b| b.damage.total_cmp(&a.damage));
+        hits.truncate(top_hits);
+
+        Self {
+            characters,
+            total_damage,
+            turns: None,
+            final_av: None,
+            total_dpav: None,
+            top_hits: hits,
+            turn_table: Vec::new(),
+        }
+    }
+
+    /// Plain text table with damage written in `numbers`; `Display` uses [`NumberFormat::Full`]
+    pub fn display(&self, numbers: NumberFormat) -> SummaryDisplay<'_> {
+        SummaryDisplay { summary: self, numbers }
+    }
+
+    pub fn to_markdown(&self, numbers: NumberFormat) -> String {
+        let mut out = String::new();
+
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n|---|---:|---:|---:|\n",
+            tr("Character"),
+            tr("Damage"),
+            tr("Share"),
+            tr("Hits")
+        ));
+        for character in &self.characters {
+            out.push_str(&format!(
+                "| {} | {} | {:.1}% | {} |\n",
+                character.name,
+                numbers.format(character.total_damage),
+                character.share * 100.0,
+                character.hits
+            ));
+        }
+        out.push_str(&format!("| **{}** | **{}** | | |\n", tr("Total"), numbers.format(self.total_damage)));
+
+        let mut stats = Vec::new();
+        if let Some(turns) = self.turns {
+            stats.push(format!("**{}:** {}", tr("Turns"), turns));
+        }
+        if let Some(av) = self.final_av {
+            stats.push(format!("**{}:** {:.2}", tr("Final AV"), av));
+        }
+        if let Some(dpav) = self.total_dpav {
+            stats.push(format!("**{}:** {}", tr("DpAV"), numbers.format(dpav)));
+        }
+        if !stats.is_empty() {
+            out.push_str(&format!("\n{}\n", stats.join(" · ")));
+        }
+
+        if !self.top_hits.is_empty() {
+            out.push_str(&format!(
+                "\n| # | {} | {} | {} | {} |\n|---:|---|---:|---:|---|\n",
+                tr("Character"),
+                tr("Damage"),
+                tr("Turn"),
+                tr("Skill")
+            ));
+            for (i, hit) in self.top_hits.iter().enumerate() {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    i + 1,
+                    hit.attacker,
+                    numbers.format(hit.damage),
+                    hit.turn.map(|t| t.to_string()).unwrap_or_default(),
+                    hit.skill.as_deref().unwrap_or("")
+                ));
+            }
+        }
+
+        if !self.turn_table.is_empty() {
+            let names: Vec<&str> = self.characters.iter().map(|c| c.name.as_str()).collect();
+            out.push_str(&format!(
+                "\n| {} | {} | {} | {} | {} |\n|---:|---|---:|{}---:|\n",
+                tr("Turn"),
+                tr("Owner"),
+                tr("AV"),
+                names.join(" | "),
+                tr("Total"),
+                "---:|".repeat(names.len())
+            ));
+            for turn in &self.turn_table {
+                let damage: Vec<String> = turn.damage.iter().map(|&d| numbers.format(d)).collect();
+                out.push_str(&format!(
+                    "| {} | {} | {:.2} | {} | {} |\n",
+                    turn.turn,
+                    turn.owner.as_deref().unwrap_or(""),
+                    turn.action_value,
+                    damage.join(" | "),
+                    numbers.format(turn.total_damage)
+                ));
+            }
+        }
+
+        out
+    }
+}
+
+impl fmt::Display for BattleSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(NumberFormat::Full).fmt(f)
+    }
+}
+
+pub struct SummaryDisplay<'a> {
+    summary: &'a BattleSummary,
+    numbers: NumberFormat,
+}
+
+impl fmt::Display for SummaryDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (summary, numbers) = (self.summary, self.numbers);
+        writeln!(f, "{:<24} {:>14} {:>7} {:>6}", tr("Character"), tr("Damage"), tr("Share"), tr("Hits"))?;
+        for character in &summary.characters {
+            writeln!(
+                f,
+                "{:<24} {:>14} {:>6.1}% {:>6}",
+                character.name,
+                numbers.format(character.total_damage),
+                character.share * 100.0,
+                character.hits
+            )?;
+        }
+        writeln!(f, "{:<24} {:>14}", tr("Total"), numbers.format(summary.total_damage))?;
+
+        if let Some(turns) = summary.turns {
+            writeln!(f, "{}: {}", tr("Turns"), turns)?;
+        }
+        if let Some(av) = summary.final_av {
+            writeln!(f, "{}: {:.2}", tr("Final AV"), av)?;
+        }
+        if let Some(dpav) = summary.total_dpav {
+            writeln!(f, "{}: {}", tr("DpAV"), numbers.format(dpav))?;
+        }
+
+        if !summary.top_hits.is_empty() {
+            writeln!(f, "\n{}", tr("Top hits"))?;
+            for (i, hit) in summary.top_hits.iter().enumerate() {
+                write!(f, "{:>3}. {:<24} {:>14}", i + 1, hit.attacker, numbers.format(hit.damage))?;
+                if let Some(turn) = hit.turn {
+                    write!(f, "  {} {}", tr("turn"), turn)?;
+                }
+                if let Some(skill) = &hit.skill {
+                    write!(f, "  {}", skill)?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        if !summary.turn_table.is_empty() {
+            write!(f, "\n{:>4} {:<16} {:>8}", tr("Turn"), tr("Owner"), tr("AV"))?;
+            for character in &summary.characters {
+                write!(f, " {:>14}", character.name)?;
+            }
+            writeln!(f, " {:>14}", tr("Total"))?;
+            for turn in &summary.turn_table {
+                write!(
+                    f,
+                    "{:>4} {:<16} {:>8.2}",
+                    turn.turn,
+                    turn.owner.as_deref().unwrap_or("-"),
+                    turn.action_value
+                )?;
+                for damage in &turn.damage {
+                    write!(f, " {:>14}", numbers.format(*damage))?;
+                }
+                writeln!(f, " {:>14}", numbers.format(turn.total_damage))?;
+            }
+        }
+        Ok(())
+    }
+}